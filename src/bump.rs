@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use crate::primatives;
+use crate::noise;
+use crate::patterns;
+use crate::material;
+
+/* Ways of making a flat surface look bumpy by tilting its normal,
+ * without changing its shape. Both the point and the normal passed to
+ * perturb() are in the shape's own space, so the bumps move, turn and
+ * stretch with the object.
+ *
+ * Noise tilts the normal by a random, smoothly changing amount (orange
+ * peel, ripples on water). scale shrinks the bumps, and amount is how
+ * far the normal can tip.
+ *
+ * HeightMap treats the brightness of a pattern (eg, an image texture) as
+ * the height of the surface, and tilts the normal down the slope. amount
+ * is how tall a brightness of 1.0 is
+ */
+#[derive(Debug, Clone)]
+pub enum Bump {
+    Noise {
+        scale: f64,
+        amount: f64,
+        octaves: usize
+    },
+    HeightMap {
+        height: Arc<dyn patterns::Pattern>,
+        amount: f64
+    }
+}
+
+//Distance either side of a point the height map is sampled to find its slope
+pub const SLOPE_EPSILON: f64 = 0.001;
+
+pub fn new_noise(scale: f64, amount: f64, octaves: usize) -> Bump {
+    return Bump::Noise {
+        scale: scale,
+        amount: amount,
+        octaves: octaves
+    }
+}
+
+pub fn new_height_map(height: impl Into<Arc<dyn patterns::Pattern>>, amount: f64) -> Bump {
+    return Bump::HeightMap {
+        height: height.into(),
+        amount: amount
+    }
+}
+
+impl Bump {
+    //Returns normalv tilted by the bump at object_point, both in object space
+    pub fn perturb(&self, object_point: primatives::PointT, normalv: primatives::Vec3T) -> primatives::Vec3T {
+        let tilt = match self {
+            Bump::Noise { scale, amount, octaves } => {
+                let p = primatives::point(object_point.x * scale, object_point.y * scale, object_point.z * scale);
+                let at = |dx: f64, dy: f64, dz: f64| -> f64 {
+                    return noise::fractal(primatives::point(p.x + dx, p.y + dy, p.z + dz), *octaves, 0.5);
+                };
+
+                //Different offsets so each axis tips on its own
+                primatives::vec3(at(0.0, 0.0, 0.0), at(31.4, 15.9, 26.5), at(-27.1, 82.8, -18.2)) * *amount
+            },
+            Bump::HeightMap { height, amount } => {
+                let at = |dx: f64, dy: f64, dz: f64| -> f64 {
+                    let p = primatives::point(object_point.x + dx, object_point.y + dy, object_point.z + dz);
+                    let c = height.color_at(p);
+                    return (c.red + c.green + c.blue) / 3.0;
+                };
+
+                let e = SLOPE_EPSILON;
+                let slope = primatives::vec3(
+                    at(e, 0.0, 0.0) - at(-e, 0.0, 0.0),
+                    at(0.0, e, 0.0) - at(0.0, -e, 0.0),
+                    at(0.0, 0.0, e) - at(0.0, 0.0, -e)
+                ) * (1.0 / (2.0 * e));
+
+                //Only the part of the slope along the surface tips the normal
+                let along_surface = slope - normalv * primatives::dot_product(slope, normalv);
+                -along_surface * *amount
+            }
+        };
+
+        return (normalv + tilt).normalized();
+    }
+}
+
+//Height maps are equal only if they use the same pattern, see Material::eq
+impl PartialEq for Bump {
+    fn eq(&self, b: &Bump) -> bool {
+        return match (self, b) {
+            (Bump::Noise { scale: s1, amount: a1, octaves: o1 },
+                Bump::Noise { scale: s2, amount: a2, octaves: o2 }) =>
+                (s1 - s2).abs() < material::EQUIVALENCY_EPSILON &&
+                (a1 - a2).abs() < material::EQUIVALENCY_EPSILON &&
+                o1 == o2,
+            (Bump::HeightMap { height: h1, amount: a1 }, Bump::HeightMap { height: h2, amount: a2 }) =>
+                Arc::ptr_eq(h1, h2) && (a1 - a2).abs() < material::EQUIVALENCY_EPSILON,
+            _ => false
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use crate::shapes::{Shape};
+use crate::shapes::bounds;
+use crate::ray;
+
+/* A bounding volume hierarchy. Objects are grouped into a tree of boxes,
+ * so a ray that misses a box can skip everything inside of it, rather
+ * than testing every object in the world.
+ */
+
+//Leaves with this few objects are not worth splitting any further
+pub const MAX_LEAF_SIZE: usize = 2;
+
+//Cost of visiting a node, relative to intersecting one object
+const TRAVERSAL_COST: f64 = 1.0;
+
+#[derive(Debug, Clone)]
+enum NodeKind {
+    Leaf(Vec<usize>),
+    Interior(usize, usize)
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    bounds: bounds::BoundingBox,
+    kind: NodeKind
+}
+
+#[derive(Debug, Clone)]
+pub struct Bvh {
+    nodes: Vec<Node>,
+
+    //Objects with infinite bounds (eg, planes) have to be tested by every ray
+    unbounded: Vec<usize>,
+
+    //Number of objects the hierarchy was built from
+    pub object_count: usize,
+
+    //Addresses of those objects, in order, to tell when they have changed
+    addresses: Vec<usize>
+}
+
+//Builds the hierarchy over the objects. The indices it returns refer to this slice
+pub fn build(objects: &[&dyn Shape]) -> Bvh {
+    let mut ret_val = Bvh {
+        nodes: vec![],
+        unbounded: vec![],
+        object_count: objects.len(),
+        addresses: objects.iter().map(|o| address_of(*o)).collect()
+    };
+
+    let mut items = vec![];
+    for (i, obj) in objects.iter().enumerate() {
+        let b = obj.bounds();
+
+        //Bounds with NaN in them (eg, from a broken transformation) count as infinite too
+        if b.is_infinite() {
+            ret_val.unbounded.push(i);
+        } else {
+            items.push((i, b));
+        }
+    }
+
+    if !items.is_empty() {
+        build_node(&mut ret_val.nodes, items);
+    }
+
+    return ret_val
+}
+
+fn address_of(s: &dyn Shape) -> usize {
+    return s as *const dyn Shape as *const () as usize;
+}
+
+fn bounds_of(items: &[(usize, bounds::BoundingBox)]) -> bounds::BoundingBox {
+    let mut ret_val = bounds::new_empty();
+    for (_, b) in items {
+        ret_val = ret_val.merge(*b);
+    }
+
+    return ret_val
+}
+
+/* Splits the items using the surface area heuristic. The chance of a
+ * ray hitting a box is roughly proportional to its surface area, so the
+ * expected cost of a split is the area of each side times the number of
+ * objects on that side. Every split along every axis is tried, and the
+ * cheapest one is used, unless leaving the items in one leaf is cheaper.
+ * Returns the index of the new node
+ */
+fn build_node(nodes: &mut Vec<Node>, mut items: Vec<(usize, bounds::BoundingBox)>) -> usize {
+    let node_bounds = bounds_of(&items);
+    let n = items.len();
+
+    let mut best: Option<(f64, usize, usize)> = None; //(cost, axis, split index)
+    if n > MAX_LEAF_SIZE {
+        let parent_area = node_bounds.surface_area();
+
+        for a in 0..3 {
+            items.sort_by(|x, y| {
+                bounds::axis(x.1.centroid(), a).total_cmp(&bounds::axis(y.1.centroid(), a))
+            });
+
+            //Area of the box around everything right of each split
+            let mut right_areas = vec![0.0; n];
+            let mut right = bounds::new_empty();
+            for i in (1..n).rev() {
+                right = right.merge(items[i].1);
+                right_areas[i] = right.surface_area();
+            }
+
+            let mut left = bounds::new_empty();
+            for i in 1..n {
+                left = left.merge(items[i - 1].1);
+                let cost = if parent_area > 0.0 {
+                    TRAVERSAL_COST + (left.surface_area() * i as f64 + right_areas[i] * (n - i) as f64) / parent_area
+                } else {
+                    TRAVERSAL_COST + n as f64
+                };
+
+                if best.is_none() || cost < best.unwrap().0 {
+                    best = Some((cost, a, i));
+                }
+            }
+        }
+    }
+
+    let (axis, split) = match best {
+        Some((cost, axis, split)) if cost < n as f64 => (axis, split),
+        _ => {
+            nodes.push(Node {
+                bounds: node_bounds,
+                kind: NodeKind::Leaf(items.iter().map(|(i, _)| *i).collect())
+            });
+            return nodes.len() - 1
+        }
+    };
+
+    items.sort_by(|x, y| {
+        bounds::axis(x.1.centroid(), axis).total_cmp(&bounds::axis(y.1.centroid(), axis))
+    });
+    let right_items = items.split_off(split);
+
+    //Reserve this node's slot before the children are added after it
+    nodes.push(Node {
+        bounds: node_bounds,
+        kind: NodeKind::Leaf(vec![])
+    });
+    let index = nodes.len() - 1;
+
+    let left = build_node(nodes, items);
+    let right = build_node(nodes, right_items);
+    nodes[index].kind = NodeKind::Interior(left, right);
+
+    return index
+}
+
+impl Bvh {
+    /* Whether objects is still exactly the list the hierarchy was built
+     * from. This only compares addresses, which is far cheaper than the
+     * ray tests the hierarchy saves
+     */
+    pub fn built_from(&self, objects: &[&dyn Shape]) -> bool {
+        return objects.len() == self.addresses.len() &&
+            objects.iter().zip(self.addresses.iter()).all(|(o, a)| address_of(*o) == *a);
+    }
+
+    /* Returns the indices of every object the ray might hit, in
+     * ascending order, so that objects are intersected in the same
+     * order as they would be without the hierarchy
+     */
+    pub fn candidates(&self, r: ray::Ray) -> Vec<usize> {
+        let mut ret_val = self.unbounded.clone();
+
+        let mut stack = vec![];
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !node.bounds.intersects(r) {
+                continue;
+            }
+
+            match &node.kind {
+                NodeKind::Leaf(objects) => ret_val.extend(objects),
+                NodeKind::Interior(left, right) => {
+                    stack.push(*left);
+                    stack.push(*right);
+                }
+            }
+        }
+
+        ret_val.sort();
+        return ret_val
+    }
+
+    pub fn depth(&self) -> usize {
+        if self.nodes.is_empty() {
+            return 0
+        }
+
+        return self.node_depth(0)
+    }
+
+    fn node_depth(&self, index: usize) -> usize {
+        return match self.nodes[index].kind {
+            NodeKind::Leaf(_) => 1,
+            NodeKind::Interior(left, right) => 1 + self.node_depth(left).max(self.node_depth(right))
+        }
+    }
+}
@@ -1,6 +1,23 @@
 use crate::matrix;
 use crate::ray;
 use crate::primatives;
+use crate::random;
+use crate::filter;
+
+/* Where in a pixel sub-pixel rays go. Both split the pixel into a grid
+ * of samples x samples cells, with one ray per cell
+ */
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Sampling {
+    //A regular grid: every ray goes through the middle of its cell
+    Grid,
+
+    /* Stratified sampling: every ray goes somewhere random in its own
+     * cell. That trades the jaggies a regular grid can leave for a
+     * little noise, while keeping the rays spread over the whole pixel
+     */
+    Jittered
+}
 
 pub struct Camera {
     pub height: usize,
@@ -9,7 +26,12 @@ pub struct Camera {
     pub transformation: matrix::Matrix4x4,
     pub pixel_size: f64,
     pub half_width: f64,
-    pub half_height: f64
+    pub half_height: f64,
+
+    //Rays per pixel along each axis, so samples * samples in total
+    pub samples: usize,
+    pub sampling: Sampling,
+    pub filter: filter::Filter
 }
 
 pub fn new(h: usize, w: usize, fov: f64) -> Camera {
@@ -35,16 +57,21 @@ pub fn new(h: usize, w: usize, fov: f64) -> Camera {
         transformation: matrix::IDENTITY_MATRIX_4X4,
         pixel_size: (half_width * 2.0) / h as f64,
         half_width: half_width,
-        half_height: half_height
+        half_height: half_height,
+        samples: 1,
+        sampling: Sampling::Grid,
+        filter: filter::Filter::Box
     }
 }
 
 impl Camera {
-    pub fn ray_at_pixel(&self, x: usize, y: usize) -> ray::Ray {
-        //(x as f64 + *MAGIC CONSTANT*) for good luck
-        //TODO: Remove magic constant?
-        let x_coord_ray_intersects = self.half_width - ((x as f64 + 0.5) * self.pixel_size);
-        let y_coord_ray_intersects = self.half_height - ((y as f64 + 0.5) * self.pixel_size);
+    /* The ray through a point on pixel (x, y). The offsets are in pixels,
+     * from the pixel's top left corner, so (0.5, 0.5) is its center.
+     * Offsets outside of 0.0 to 1.0 land on neighboring pixels
+     */
+    pub fn ray_at_pixel(&self, x: usize, y: usize, x_offset: f64, y_offset: f64) -> ray::Ray {
+        let x_coord_ray_intersects = self.half_width - ((x as f64 + x_offset) * self.pixel_size);
+        let y_coord_ray_intersects = self.half_height - ((y as f64 + y_offset) * self.pixel_size);
 
         let point_ray_intersects = 
             self.transformation.inverse() * primatives::point(x_coord_ray_intersects, y_coord_ray_intersects, -1.0);
@@ -55,4 +82,33 @@ impl Camera {
 
         return ray::new(ray_origin, ray_direction);
     }
+
+    /* The (x offset, y offset, weight) of every ray to cast for pixel
+     * (x, y). The pixel's color is the weighted average of the colors the
+     * rays see. Jitter is seeded by the pixel, so renders are repeatable
+     */
+    pub fn pixel_samples(&self, x: usize, y: usize) -> Vec<(f64, f64, f64)> {
+        let n = self.samples.max(1);
+        let mut ret_val = Vec::with_capacity(n * n);
+
+        for i in 0..n {
+            for j in 0..n {
+                let (du, dv) = match self.sampling {
+                    Sampling::Grid => (0.5, 0.5),
+                    Sampling::Jittered => (
+                        random::jitter(&[x as u64, y as u64, i as u64, j as u64, 0]),
+                        random::jitter(&[x as u64, y as u64, i as u64, j as u64, 1])
+                    )
+                };
+
+                //A spot in the unit square, spread along the filter's curve
+                let (fx, x_sign) = self.filter.sample((i as f64 + du) / n as f64);
+                let (fy, y_sign) = self.filter.sample((j as f64 + dv) / n as f64);
+
+                ret_val.push((0.5 + fx, 0.5 + fy, x_sign * y_sign));
+            }
+        }
+
+        return ret_val
+    }
 }
\ No newline at end of file
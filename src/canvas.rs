@@ -1,21 +1,47 @@
 use crate::color;
+use crate::png;
+use crate::tonemap;
 use std::fs;
+use std::io;
 use std::io::Write;
 
+/* Image file formats a canvas can be written as. P3 is the plain text
+ * PPM, easy to read but huge. P6 is the same thing with the numbers
+ * written as raw bytes, and Png is compressed. All three clamp colors
+ * to 0.0 to 1.0 and keep 8 bits of each.
+ *
+ * Pfm (Portable Float Map) and Hdr (Radiance RGBE) keep colors brighter
+ * than 1.0, for adjusting exposure later. Pfm stores every channel as a
+ * 32 bit float, Hdr shares one exponent between the three channels of a
+ * pixel, which is 4 bytes a pixel but only about 1% precise
+ */
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Format {
+    P3,
+    P6,
+    Png,
+    Pfm,
+    Hdr
+}
+
 #[derive(Debug, Clone)]
 pub struct Canvas {
     pub origin: (i32, i32),
     pub width: usize,
     pub height: usize,
     pub contents: Vec<color::Color>,
+
+    //Applied to every pixel written to an 8 bit format (P3, P6 and Png)
+    pub tone_map: tonemap::ToneMap
 }
 
 pub fn new(w: usize, h: usize) -> Canvas {
     return Canvas {
-        origin: (w as i32 / 2 as i32, h as i32 / 2 as i32),
+        origin: (w as i32 / 2, h as i32 / 2),
         width: w,
         height: h,
         contents: vec![color::new(0.0, 0.0, 0.0); w * h],
+        tone_map: tonemap::new_linear()
     };
 }
 
@@ -29,9 +55,7 @@ impl Canvas {
         let x = x_arg + self.origin.0;
         let y = y_arg + self.origin.1;
 
-        if x < 0 || y < 0 {
-            panic!("Coordinate out of bounds ({}, {})", x_arg, y_arg);
-        } else if x as usize > self.width || y as usize > self.height {
+        if x < 0 || y < 0 || x as usize > self.width || y as usize > self.height {
             panic!("Coordinate out of bounds ({}, {})", x_arg, y_arg);
         }
 
@@ -45,20 +69,75 @@ impl Canvas {
 
 
     pub fn write_to_ppm(&self, filename: &str) {
-        let mut file = fs::File::create(filename).expect("PPM file creation failed");
+        self.write_to_file(filename, Format::P3).expect("Failed to write PPM file");
+    }
+
+    pub fn write_to_file(&self, filename: &str, format: Format) -> io::Result<()> {
+        let file = fs::File::create(filename)?;
+        return self.write_to(file, format);
+    }
+
+    /* Writes the image to anything that can be written to, eg, a file,
+     * stdout, or a Vec<u8> in memory. Writes are buffered, so there is
+     * no need to pass in a BufWriter
+     */
+    pub fn write_to(&self, w: impl Write, format: Format) -> io::Result<()> {
+        let mut out = io::BufWriter::new(w);
+
+        match format {
+            Format::P3 => {
+                write!(out, "P3\n{} {}\n255\n", self.width, self.height)?;
+                for pixel in self.to_rgb8().chunks(3) {
+                    writeln!(out, "{} {} {}", pixel[0], pixel[1], pixel[2])?;
+                }
+            },
+            Format::P6 => {
+                write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+                out.write_all(&self.to_rgb8())?;
+            },
+            Format::Png => {
+                out.write_all(&png::encode(self.width, self.height, &self.to_rgb8()))?;
+            },
+            Format::Pfm => {
+                //A negative scale means little endian. Rows go bottom to top.
+                //An empty canvas has no rows to split up, just the header
+                write!(out, "PF\n{} {}\n-1.0\n", self.width, self.height)?;
+                if self.width > 0 {
+                    for row in self.contents.chunks(self.width).rev() {
+                        for i in row {
+                            out.write_all(&(i.red as f32).to_le_bytes())?;
+                            out.write_all(&(i.green as f32).to_le_bytes())?;
+                            out.write_all(&(i.blue as f32).to_le_bytes())?;
+                        }
+                    }
+                }
+            },
+            Format::Hdr => {
+                //Rows go top to bottom. Scanlines are written flat, not run length encoded
+                write!(out, "#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y {} +X {}\n", self.height, self.width)?;
+                for i in &self.contents {
+                    out.write_all(&i.to_rgbe())?;
+                }
+            }
+        }
 
-        let header = format!("P3\n{} {}\n255\n", self.width, self.height);
-        file.write_all(header.as_bytes()).expect("Failed to write PPM header");
+        return out.flush();
+    }
 
-        for i in &self.contents {
-            let pixel_in_255 = i.scaled_from_1_to_255();
-            let pixel_as_text = format!("{} {} {}\n", pixel_in_255.red, pixel_in_255.green, pixel_in_255.blue);
-            file.write_all(pixel_as_text.as_bytes()).expect("Failed to write pixel");
+    //Every pixel, run through the tone map, as 3 bytes (red, green, blue), top row first
+    pub fn to_rgb8(&self) -> Vec<u8> {
+        let mut ret_val = Vec::with_capacity(self.contents.len() * 3);
+        for (index, i) in self.contents.iter().enumerate() {
+            let (x, y) = (index % self.width, index / self.width);
+            ret_val.extend(self.tone_map.to_rgb8(*i, x, y));
         }
+
+        return ret_val
     }
 
     pub fn antialiased(&self, r: i32) -> Canvas {
         let mut ret_val = new(self.width, self.height);
+        ret_val.tone_map = self.tone_map;
 
         for x_loc in 0..self.width {
             for y_loc in 0..self.height {
@@ -92,9 +171,9 @@ impl Canvas {
                     let c = self.contents[loc as usize];
                     avg_color = avg_color + c;
                 }
-                avg_color.red = avg_color.red / size;
-                avg_color.green = avg_color.green / size;
-                avg_color.blue = avg_color.blue / size;
+                avg_color.red /= size;
+                avg_color.green /= size;
+                avg_color.blue /= size;
 
                 ret_val.contents[location] = avg_color;
             }
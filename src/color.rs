@@ -13,6 +13,9 @@ pub const BLACK: Color = Color {
     blue: 0.0
 };
 
+//The brightest channel RGBE can store: a 255 byte with the largest exponent, 2^127
+pub const MAX_RGBE: f64 = 255.0 / 256.0 * 1.7014118346046923e38;
+
 pub fn new(r:f64, g:f64, b:f64) -> Color {
     return Color {
         red:r,
@@ -29,6 +32,39 @@ impl Color {
             blue: scale_num_1_to_255(self.blue)
         };
     }
+
+    /* Radiance's RGBE: each channel is a byte, all scaled by one shared
+     * power of two, stored as the fourth byte (offset by 128). The
+     * brightest channel decides the exponent, so dim channels next to
+     * a bright one lose precision. Negative channels become 0
+     */
+    pub fn to_rgbe(&self) -> [u8; 4] {
+        //Infinite channels are clamped to the brightest value RGBE can hold, NaN ones become 0
+        let finite = |c: f64| -> f64 {
+            return if c.is_nan() { 0.0 } else { c.min(MAX_RGBE) };
+        };
+        let (red, green, blue) = (finite(self.red), finite(self.green), finite(self.blue));
+
+        let brightest = red.max(green).max(blue);
+        if brightest < 1e-32 {
+            return [0, 0, 0, 0];
+        }
+
+        //brightest = mantissa * 2^exponent, with mantissa in [0.5, 1)
+        let mut exponent = brightest.log2().floor() as i32 + 1;
+        if brightest / 2.0_f64.powi(exponent) >= 1.0 {
+            exponent += 1;
+        } else if brightest / 2.0_f64.powi(exponent) < 0.5 {
+            exponent -= 1;
+        }
+
+        let scale = 256.0 / 2.0_f64.powi(exponent);
+        let byte = |c: f64| -> u8 {
+            return (c.max(0.0) * scale).min(255.0) as u8;
+        };
+
+        return [byte(red), byte(green), byte(blue), (exponent + 128).clamp(0, 255) as u8];
+    }
 }
 
 fn scale_num_1_to_255(val:f64) -> f64 {
@@ -0,0 +1,130 @@
+use std::sync::OnceLock;
+
+/* Reconstruction filters, for turning many samples of a pixel into one
+ * color. Each is a curve centered on the pixel (applied along x and y
+ * separately), giving how much a sample that far away counts. Box counts
+ * everything within half a pixel the same (a plain average), Tent and
+ * Gaussian fall off smoothly and reach into the neighboring pixels, and
+ * Mitchell dips slightly below zero around its edges, which keeps edges
+ * sharp.
+ *
+ * Rather than weighing evenly spread samples, samples are spread out
+ * following the curve, so most land where it is tallest. Then every
+ * sample counts the same, apart from Mitchell's negative parts which
+ * count as -1
+ */
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Filter {
+    Box,
+    Tent,
+    Gaussian,
+    Mitchell
+}
+
+const GAUSSIAN_SIGMA: f64 = 0.5;
+
+//Entries in the tables used to spread samples along the Gaussian and Mitchell curves
+const TABLE_SIZE: usize = 1024;
+
+impl Filter {
+    //How many pixels from the center the curve reaches
+    pub fn radius(&self) -> f64 {
+        return match self {
+            Filter::Box => 0.5,
+            Filter::Tent => 1.0,
+            Filter::Gaussian => 1.5,
+            Filter::Mitchell => 2.0
+        };
+    }
+
+    //Height of the curve at x pixels from the center
+    pub fn weight(&self, x: f64) -> f64 {
+        let x = x.abs();
+        if x > self.radius() {
+            return 0.0
+        }
+
+        return match self {
+            Filter::Box => 1.0,
+            Filter::Tent => 1.0 - x,
+            Filter::Gaussian => {
+                //Shifted down so it reaches exactly zero at the radius
+                let g = |x: f64| -> f64 { (-(x * x) / (2.0 * GAUSSIAN_SIGMA * GAUSSIAN_SIGMA)).exp() };
+                g(x) - g(self.radius())
+            },
+            Filter::Mitchell => {
+                //Mitchell and Netravali's recommended B = C = 1/3
+                let (b, c) = (1.0 / 3.0, 1.0 / 3.0);
+                if x < 1.0 {
+                    ((12.0 - 9.0 * b - 6.0 * c) * x * x * x + (-18.0 + 12.0 * b + 6.0 * c) * x * x + (6.0 - 2.0 * b)) / 6.0
+                } else {
+                    ((-b - 6.0 * c) * x * x * x + (6.0 * b + 30.0 * c) * x * x + (-12.0 * b - 48.0 * c) * x + (8.0 * b + 24.0 * c)) / 6.0
+                }
+            }
+        };
+    }
+
+    /* Moves u, evenly spread between 0.0 and 1.0, to a distance from the
+     * center (-radius to radius) spread out like the curve. Also returns
+     * the sign of the curve there
+     */
+    pub fn sample(&self, u: f64) -> (f64, f64) {
+        let u = u.clamp(0.0, 1.0);
+
+        let x = match self {
+            Filter::Box => u - 0.5,
+            Filter::Tent => {
+                if u < 0.5 {
+                    (2.0 * u).sqrt() - 1.0
+                } else {
+                    1.0 - (2.0 - 2.0 * u).sqrt()
+                }
+            },
+            Filter::Gaussian | Filter::Mitchell => self.sample_from_table(u)
+        };
+
+        let sign = if self.weight(x) < 0.0 { -1.0 } else { 1.0 };
+        return (x, sign);
+    }
+
+    //Running totals of the size of the curve, from -radius up to each entry
+    fn table(&self) -> &'static Vec<f64> {
+        static GAUSSIAN: OnceLock<Vec<f64>> = OnceLock::new();
+        static MITCHELL: OnceLock<Vec<f64>> = OnceLock::new();
+
+        let cell = match self {
+            Filter::Mitchell => &MITCHELL,
+            _ => &GAUSSIAN
+        };
+
+        return cell.get_or_init(|| {
+            let r = self.radius();
+            let step = 2.0 * r / TABLE_SIZE as f64;
+
+            let mut ret_val = vec![0.0; TABLE_SIZE + 1];
+            for i in 0..TABLE_SIZE {
+                let middle = -r + (i as f64 + 0.5) * step;
+                ret_val[i + 1] = ret_val[i] + self.weight(middle).abs() * step;
+            }
+
+            let total = ret_val[TABLE_SIZE];
+            for v in ret_val.iter_mut() {
+                *v /= total;
+            }
+
+            ret_val
+        });
+    }
+
+    fn sample_from_table(&self, u: f64) -> f64 {
+        let table = self.table();
+        let r = self.radius();
+
+        //The last entry at or below u, then the rest of the way to the next linearly
+        let i = table.partition_point(|v| *v <= u).clamp(1, TABLE_SIZE) - 1;
+        let span = table[i + 1] - table[i];
+        let fraction = if span > 0.0 { (u - table[i]) / span } else { 0.0 };
+
+        return -r + (i as f64 + fraction) * (2.0 * r / TABLE_SIZE as f64);
+    }
+}
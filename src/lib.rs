@@ -3,10 +3,21 @@ pub mod primatives;
 pub mod matrix;
 pub mod color;
 pub mod canvas;
+pub mod zlib;
+pub mod png;
+pub mod ppm;
+pub mod tonemap;
 pub mod ray;
 pub mod shapes;
 pub mod light;
 pub mod material;
+pub mod bump;
+pub mod patterns;
 pub mod shading;
 pub mod world;
-pub mod camera;
\ No newline at end of file
+pub mod camera;
+pub mod filter;
+pub mod random;
+pub mod noise;
+pub mod bvh;
+pub mod obj;
\ No newline at end of file
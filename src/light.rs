@@ -1,10 +1,109 @@
 use crate::color;
 use crate::primatives;
+use crate::random;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LightKind {
+    //All light comes from a single, infinitely small point
+    Point,
+
+    /* A rectangle, with one corner at `corner` and sides along `uvec`
+     * and `vvec`. It is split into a grid of `samples` x `samples` cells,
+     * and each cell is lit from a jittered point inside of it
+     */
+    Area {
+        corner: primatives::PointT,
+        uvec: primatives::Vec3T,
+        vvec: primatives::Vec3T,
+        samples: usize
+    },
+
+    /* A light so far away (eg, the sun) that all of its rays are
+     * parallel, travelling along `direction`. It does not fade with
+     * distance, and its location is meaningless
+     */
+    Directional {
+        direction: primatives::Vec3T
+    },
+
+    /* A point light that only shines within a cone around `direction`.
+     * Inside of the inner cone it is at full strength, and it fades out
+     * smoothly between the inner and the outer cone. Both angles are
+     * measured from `direction` to the side of the cone
+     */
+    Spot {
+        direction: primatives::Vec3T,
+        inner_cone_radians: f64,
+        outer_cone_radians: f64
+    }
+}
+
+//How the diffuse and specular light falls off as it gets further from the light
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Attenuation {
+    //Full strength at any distance
+    None,
+
+    //See Polynomial, made by new_attenuation()
+    Polynomial(Polynomial),
+
+    /* Physically based falloff, intensity is divided by (d * d). Within
+     * one unit of the light it stays at full strength, rather than
+     * blowing up towards infinity
+     */
+    InverseSquare
+}
+
+/* Intensity is divided by (constant + linear * d + quadratic * d * d).
+ * The coefficients are private so they can only come from
+ * new_attenuation(), which checks them
+ */
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Polynomial {
+    constant: f64,
+    linear: f64,
+    quadratic: f64
+}
+
+//Smallest value the polynomial's divisor is allowed to reach
+pub const ATTENUATION_EPSILON: f64 = 0.0001;
+
+/* A polynomial falloff. At least one of the coefficients has to be above
+ * zero (and none below it), otherwise the light is infinitely bright
+ */
+pub fn new_attenuation(constant: f64, linear: f64, quadratic: f64) -> Attenuation {
+    if constant < 0.0 || linear < 0.0 || quadratic < 0.0 {
+        panic!("Attenuation coefficients can not be negative");
+    } else if constant == 0.0 && linear == 0.0 && quadratic == 0.0 {
+        panic!("At least one attenuation coefficient must be above zero");
+    }
+
+    return Attenuation::Polynomial(Polynomial {
+        constant: constant,
+        linear: linear,
+        quadratic: quadratic
+    })
+}
+
+impl Polynomial {
+    //(constant, linear, quadratic)
+    pub fn coefficients(&self) -> (f64, f64, f64) {
+        return (self.constant, self.linear, self.quadratic)
+    }
+
+    pub fn divisor(&self, d: f64) -> f64 {
+        return (self.constant + self.linear * d + self.quadratic * d * d).max(ATTENUATION_EPSILON)
+    }
+}
 
 #[derive(Debug, Copy, Clone)]
 pub struct Light {
     pub intensity: color::Color,
-    pub location: primatives::PointT
+    pub location: primatives::PointT, //For area lights, the center of the rectangle
+    pub kind: LightKind,
+
+    //Directional lights are infinitely far away, and ignore this
+    pub attenuation: Attenuation
 }
 
 pub fn new(i: color::Color, l: primatives::PointT) -> Light {
@@ -13,11 +112,168 @@ pub fn new(i: color::Color, l: primatives::PointT) -> Light {
     return Light {
         intensity: i,
         location: l,
+        kind: LightKind::Point,
+        attenuation: Attenuation::None
+    }
+}
+
+pub fn new_area(i: color::Color, corner: primatives::PointT, uvec: primatives::Vec3T,
+vvec: primatives::Vec3T, samples: usize) -> Light {
+    corner.check_type(primatives::TYPE_PNT);
+    uvec.check_type(primatives::TYPE_VEC);
+    vvec.check_type(primatives::TYPE_VEC);
+
+    if samples == 0 {
+        panic!("An area light needs at least one sample");
+    }
+
+    return Light {
+        intensity: i,
+        location: corner + uvec * 0.5 + vvec * 0.5,
+        kind: LightKind::Area {
+            corner: corner,
+            uvec: uvec,
+            vvec: vvec,
+            samples: samples
+        },
+        attenuation: Attenuation::None
+    }
+}
+
+//A zero length direction can not be normalized, and would light nothing
+fn check_direction(direction: primatives::Vec3T) {
+    direction.check_type(primatives::TYPE_VEC);
+
+    let length = direction.magnitude();
+    if length == 0.0 || !length.is_finite() {
+        panic!("A light's direction needs a length, got ({}, {}, {})", direction.x, direction.y, direction.z);
+    }
+}
+
+pub fn new_directional(i: color::Color, direction: primatives::Vec3T) -> Light {
+    check_direction(direction);
+
+    return Light {
+        intensity: i,
+        location: primatives::point(0.0, 0.0, 0.0),
+        kind: LightKind::Directional {
+            direction: direction.normalized()
+        },
+        attenuation: Attenuation::None
+    }
+}
+
+pub fn new_spot(i: color::Color, l: primatives::PointT, direction: primatives::Vec3T,
+inner_cone_radians: f64, outer_cone_radians: f64) -> Light {
+    l.check_type(primatives::TYPE_PNT);
+    check_direction(direction);
+
+    if inner_cone_radians > outer_cone_radians {
+        panic!("A spot light's inner cone must fit inside of its outer cone");
+    }
+
+    return Light {
+        intensity: i,
+        location: l,
+        kind: LightKind::Spot {
+            direction: direction.normalized(),
+            inner_cone_radians: inner_cone_radians,
+            outer_cone_radians: outer_cone_radians
+        },
+        attenuation: Attenuation::None
+    }
+}
+
+impl Light {
+    //Normalized vector pointing from p towards the light
+    pub fn direction_from(&self, p: primatives::PointT) -> primatives::Vec3T {
+        return match self.kind {
+            LightKind::Directional { direction } => -direction,
+            _ => (self.location - p).normalized()
+        }
+    }
+
+    //How far a shadow ray from p has to travel before it reaches the light
+    pub fn distance_from(&self, p: primatives::PointT) -> f64 {
+        return match self.kind {
+            LightKind::Directional { .. } => f64::INFINITY,
+            _ => (self.location - p).magnitude()
+        }
+    }
+
+    /* The fraction of the light's diffuse and specular contribution left
+     * by the time it has travelled to p
+     */
+    pub fn attenuation_at(&self, p: primatives::PointT) -> f64 {
+        let d = self.distance_from(p);
+        if d == f64::INFINITY {
+            return 1.0
+        }
+
+        return match self.attenuation {
+            Attenuation::None => 1.0,
+            Attenuation::Polynomial(p) => 1.0 / p.divisor(d),
+            Attenuation::InverseSquare => 1.0 / (d * d).max(1.0)
+        }
+    }
+
+    //The intensity of the light that actually reaches p
+    pub fn intensity_at(&self, p: primatives::PointT) -> color::Color {
+        let (direction, inner, outer) = match self.kind {
+            LightKind::Spot { direction, inner_cone_radians, outer_cone_radians } =>
+                (direction, inner_cone_radians, outer_cone_radians),
+            _ => return self.intensity
+        };
+
+        //Compare cosines rather than angles, to avoid calling acos()
+        let cos_angle = primatives::dot_product(-self.direction_from(p), direction);
+        let (cos_inner, cos_outer) = (inner.cos(), outer.cos());
+
+        if cos_angle >= cos_inner {
+            return self.intensity
+        } else if cos_angle <= cos_outer {
+            return color::BLACK
+        }
+
+        //Smoothstep between the two cones, so the edge of the spot is not harsh
+        let t = (cos_angle - cos_outer) / (cos_inner - cos_outer);
+        return self.intensity * (t * t * (3.0 - 2.0 * t))
+    }
+
+    /* Breaks the light up into point lights, which together give off
+     * the same amount of light. The point being lit seeds the jitter,
+     * so neighbouring points see slightly different samples (which hides
+     * banding in the penumbra), but a given point always sees the same ones
+     */
+    pub fn samples(&self, lit_point: primatives::PointT) -> Vec<Light> {
+        let (corner, uvec, vvec, samples) = match self.kind {
+            LightKind::Area { corner, uvec, vvec, samples } => (corner, uvec, vvec, samples),
+            _ => return vec![*self]
+        };
+
+        let intensity = self.intensity * (1.0 / (samples * samples) as f64);
+        let (x, y, z) = (lit_point.x.to_bits(), lit_point.y.to_bits(), lit_point.z.to_bits());
+
+        let mut ret_val = vec![];
+        for u in 0..samples {
+            for v in 0..samples {
+                let (u_seed, v_seed) = (u as u64, v as u64);
+                let u_offset = (u as f64 + random::jitter(&[x, y, z, u_seed, v_seed, 0])) / samples as f64;
+                let v_offset = (v as f64 + random::jitter(&[x, y, z, u_seed, v_seed, 1])) / samples as f64;
+
+                let mut sample = new(intensity, corner + uvec * u_offset + vvec * v_offset);
+                sample.attenuation = self.attenuation;
+                ret_val.push(sample);
+            }
+        }
+
+        return ret_val
     }
 }
 
 impl PartialEq for Light {
     fn eq(&self, l:&Light) -> bool {
-        return self.intensity == l.intensity && self.location == l.location
+        return self.intensity == l.intensity && self.location == l.location &&
+            self.kind == l.kind && self.attenuation == l.attenuation
     }
-}
\ No newline at end of file
+}
@@ -1,12 +1,36 @@
+use std::sync::Arc;
+
 use crate::color;
+use crate::primatives;
+use crate::patterns;
+use crate::bump;
 
-#[derive(Debug, Copy, Clone)]
+/* Materials used to be Copy. Patterns and bumps are shared behind an
+ * Arc, so that had to go: this is a deliberate breaking change, and
+ * materials now have to be cloned where they used to be copied
+ */
+#[derive(Debug, Clone)]
 pub struct Material {
     pub color: color::Color,
     pub ambient: f64,
     pub diffuse: f64,
     pub specular: f64,
-    pub shininess: f64
+    pub shininess: f64,
+
+    //0.0 is completely matte, 1.0 is a perfect mirror
+    pub reflective: f64,
+
+    //0.0 is opaque, 1.0 lets all light through
+    pub transparency: f64,
+
+    //How much light bends entering the material. 1.0 is a vacuum, 1.5 glass
+    pub refractive_index: f64,
+
+    //When set, the pattern is used in place of color
+    pub pattern: Option<Arc<dyn patterns::Pattern>>,
+
+    //When set, tilts the surface normal to fake small bumps
+    pub bump: Option<bump::Bump>
 }
 
 pub fn new(c: color::Color, a: f64, d: f64, sp: f64, sh: f64) -> Material {
@@ -15,7 +39,12 @@ pub fn new(c: color::Color, a: f64, d: f64, sp: f64, sh: f64) -> Material {
         ambient: a,
         diffuse: d,
         specular: sp,
-        shininess: sh
+        shininess: sh,
+        reflective: 0.0,
+        transparency: 0.0,
+        refractive_index: 1.0,
+        pattern: None,
+        bump: None
     }
 }
 
@@ -25,7 +54,22 @@ pub fn new_default() -> Material {
         ambient: 0.1,
         diffuse: 0.9,
         specular: 0.9,
-        shininess:  200.0
+        shininess:  200.0,
+        reflective: 0.0,
+        transparency: 0.0,
+        refractive_index: 1.0,
+        pattern: None,
+        bump: None
+    }
+}
+
+impl Material {
+    //The color of the material at a point in the space of the object it is on
+    pub fn color_at(&self, object_point: primatives::PointT) -> color::Color {
+        return match &self.pattern {
+            Some(p) => p.color_at(object_point),
+            None => self.color
+        };
     }
 }
 
@@ -35,6 +79,20 @@ impl PartialEq for Material {
         return (self.ambient - m.ambient).abs() < EQUIVALENCY_EPSILON &&
             (self.diffuse - m.diffuse).abs() < EQUIVALENCY_EPSILON &&
             (self.specular - m.specular).abs() < EQUIVALENCY_EPSILON &&
-            (self.shininess - m.shininess).abs() < EQUIVALENCY_EPSILON
+            (self.shininess - m.shininess).abs() < EQUIVALENCY_EPSILON &&
+            (self.reflective - m.reflective).abs() < EQUIVALENCY_EPSILON &&
+            (self.transparency - m.transparency).abs() < EQUIVALENCY_EPSILON &&
+            (self.refractive_index - m.refractive_index).abs() < EQUIVALENCY_EPSILON &&
+            same_pattern(&self.pattern, &m.pattern) &&
+            self.bump == m.bump
+    }
+}
+
+//Patterns can not be compared by what they draw, so only the same pattern is equal
+fn same_pattern(p1: &Option<Arc<dyn patterns::Pattern>>, p2: &Option<Arc<dyn patterns::Pattern>>) -> bool {
+    return match (p1, p2) {
+        (Some(p1), Some(p2)) => Arc::ptr_eq(p1, p2),
+        (None, None) => true,
+        _ => false
     }
 }
\ No newline at end of file
@@ -77,7 +77,7 @@ impl Matrix4x4 {
     pub fn cofactor(&self, r:usize, c:usize) -> f64 {
         let s = self.minor(r, c);
         return
-            if ((r % 2 == 0) && (c % 2 == 0)) || ((r % 2 != 0) && (c % 2 != 0)) {
+            if (r + c).is_multiple_of(2) {
                 s
             } else {
                 -s
@@ -118,7 +118,7 @@ impl Matrix4x4 {
 
         for row_iter in 0..4 {
             for col_iter in 0..4 {
-                ret_val[row_iter][col_iter] = ret_val[row_iter][col_iter] / d;
+                ret_val[row_iter][col_iter] /= d;
             }
         }
  
@@ -0,0 +1,103 @@
+use crate::primatives;
+use crate::random;
+
+/* Gradient (Perlin) noise. Every whole-number point in space gets a
+ * gradient picked pseudo-randomly from the twelve directions towards the
+ * edges of a cube, and points in between blend the gradients of the eight
+ * corners around them. The result changes smoothly, is 0.0 on the whole
+ * numbers, and stays roughly between -1.0 and 1.0. Like the rest of the
+ * random module, it is stateless, so the same point always gives the
+ * same value
+ */
+
+const GRADIENTS: [(f64, f64, f64); 12] = [
+    (1.0, 1.0, 0.0), (-1.0, 1.0, 0.0), (1.0, -1.0, 0.0), (-1.0, -1.0, 0.0),
+    (1.0, 0.0, 1.0), (-1.0, 0.0, 1.0), (1.0, 0.0, -1.0), (-1.0, 0.0, -1.0),
+    (0.0, 1.0, 1.0), (0.0, -1.0, 1.0), (0.0, 1.0, -1.0), (0.0, -1.0, -1.0)
+];
+
+//Eases from 0 to 1 with no sudden change in slope at either end
+fn fade(t: f64) -> f64 {
+    return t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+}
+
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    return a + t * (b - a);
+}
+
+//The gradient at corner (x, y, z), dotted with the offset from that corner
+fn corner(x: i64, y: i64, z: i64, dx: f64, dy: f64, dz: f64) -> f64 {
+    let index = (random::jitter(&[x as u64, y as u64, z as u64]) * 12.0) as usize;
+    let (gx, gy, gz) = GRADIENTS[index];
+    return gx * dx + gy * dy + gz * dz;
+}
+
+pub fn perlin(p: primatives::PointT) -> f64 {
+    let (x0, y0, z0) = (p.x.floor(), p.y.floor(), p.z.floor());
+    let (dx, dy, dz) = (p.x - x0, p.y - y0, p.z - z0);
+    let (x, y, z) = (x0 as i64, y0 as i64, z0 as i64);
+
+    let (u, v, w) = (fade(dx), fade(dy), fade(dz));
+
+    let near = lerp(v,
+        lerp(u, corner(x, y, z, dx, dy, dz), corner(x + 1, y, z, dx - 1.0, dy, dz)),
+        lerp(u, corner(x, y + 1, z, dx, dy - 1.0, dz), corner(x + 1, y + 1, z, dx - 1.0, dy - 1.0, dz)));
+
+    let far = lerp(v,
+        lerp(u, corner(x, y, z + 1, dx, dy, dz - 1.0), corner(x + 1, y, z + 1, dx - 1.0, dy, dz - 1.0)),
+        lerp(u, corner(x, y + 1, z + 1, dx, dy - 1.0, dz - 1.0), corner(x + 1, y + 1, z + 1, dx - 1.0, dy - 1.0, dz - 1.0)));
+
+    return lerp(w, near, far);
+}
+
+/* Layers of noise (octaves), each twice as detailed and persistence times
+ * as strong as the one before. Rescaled so it stays roughly within -1.0
+ * and 1.0 no matter how many octaves are used
+ */
+pub fn fractal(p: primatives::PointT, octaves: usize, persistence: f64) -> f64 {
+    let mut total = 0.0;
+    let mut frequency = 1.0;
+    let mut amplitude = 1.0;
+    let mut max_value = 0.0;
+
+    for _ in 0..octaves {
+        let scaled = primatives::point(p.x * frequency, p.y * frequency, p.z * frequency);
+        total += perlin(scaled) * amplitude;
+        max_value += amplitude;
+
+        amplitude *= persistence;
+        frequency *= 2.0;
+    }
+
+    if max_value == 0.0 {
+        return 0.0
+    }
+
+    return total / max_value
+}
+
+/* Like fractal noise, but every octave only ever adds. The creases where
+ * the noise crosses 0.0 give a swirling, billowing look, like smoke or the
+ * veins in marble. Between 0.0 and roughly 1.0
+ */
+pub fn turbulence(p: primatives::PointT, octaves: usize) -> f64 {
+    let mut total = 0.0;
+    let mut frequency = 1.0;
+    let mut amplitude = 1.0;
+    let mut max_value = 0.0;
+
+    for _ in 0..octaves {
+        let scaled = primatives::point(p.x * frequency, p.y * frequency, p.z * frequency);
+        total += perlin(scaled).abs() * amplitude;
+        max_value += amplitude;
+
+        amplitude *= 0.5;
+        frequency *= 2.0;
+    }
+
+    if max_value == 0.0 {
+        return 0.0
+    }
+
+    return total / max_value
+}
@@ -0,0 +1,289 @@
+use std::fmt;
+use std::fs;
+
+use crate::primatives;
+use crate::shapes::{Shape};
+use crate::shapes::triangle;
+
+/* Reads Wavefront OBJ meshes. Supported statements are vertices (v),
+ * vertex normals (vn), texture coordinates (vt), faces (f) and groups
+ * (g and o). Faces with more than three corners are split into a fan of
+ * triangles around the first corner. Faces that have a normal at every
+ * corner become smooth triangles, everything else becomes flat triangles.
+ * Faces with texture coordinates at every corner keep them on the
+ * triangles, see Triangle::texture_coords_at(). Groups that share a name
+ * are merged into one.
+ *
+ * Anything else (materials, smoothing groups, lines, ...) is skipped, and
+ * listed in ObjFile::ignored so the caller can see what was left out.
+ */
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObjError {
+    //The file could not be read at all
+    Io(String),
+
+    //A supported statement was malformed
+    Parse {
+        line: usize,
+        message: String
+    }
+}
+
+impl fmt::Display for ObjError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            ObjError::Io(message) => write!(f, "Could not read OBJ file: {}", message),
+            ObjError::Parse { line, message } => write!(f, "OBJ line {}: {}", line, message)
+        }
+    }
+}
+
+//A line that was skipped over, because it is not supported
+#[derive(Debug, Clone, PartialEq)]
+pub struct IgnoredLine {
+    pub line: usize,
+    pub text: String
+}
+
+//The triangles from every g or o statement with the same name
+#[derive(Debug, Clone)]
+pub struct ObjGroup {
+    pub name: String,
+    pub triangles: Vec<triangle::Triangle>,
+    pub smooth_triangles: Vec<triangle::SmoothTriangle>
+}
+
+#[derive(Debug, Clone)]
+pub struct ObjFile {
+    pub vertices: Vec<primatives::PointT>,
+    pub normals: Vec<primatives::Vec3T>,
+    pub texture_coords: Vec<(f64, f64)>,
+
+    //Faces before the first g or o go into a group with an empty name
+    pub groups: Vec<ObjGroup>,
+    pub ignored: Vec<IgnoredLine>
+}
+
+fn new_group(name: &str) -> ObjGroup {
+    return ObjGroup {
+        name: name.to_string(),
+        triangles: vec![],
+        smooth_triangles: vec![]
+    }
+}
+
+pub fn load(filename: &str) -> Result<ObjFile, ObjError> {
+    return match fs::read_to_string(filename) {
+        Ok(text) => parse(&text),
+        Err(e) => Err(ObjError::Io(format!("{}: {}", filename, e)))
+    }
+}
+
+fn parse_error(line: usize, message: String) -> ObjError {
+    return ObjError::Parse {
+        line: line,
+        message: message
+    }
+}
+
+fn parse_numbers(line: usize, args: &[&str], count: usize) -> Result<Vec<f64>, ObjError> {
+    if args.len() < count {
+        return Err(parse_error(line, format!("Expected {} numbers, found {}", count, args.len())));
+    }
+
+    let mut ret_val = vec![];
+    for a in args {
+        match a.parse::<f64>() {
+            Ok(n) => ret_val.push(n),
+            Err(_) => return Err(parse_error(line, format!("'{}' is not a number", a)))
+        }
+    }
+
+    return Ok(ret_val)
+}
+
+/* Indices count from 1. Negative indices count backwards
+ * from the most recently defined element
+ */
+fn parse_index(line: usize, text: &str, len: usize, what: &str) -> Result<usize, ObjError> {
+    let i = match text.parse::<i64>() {
+        Ok(i) => i,
+        Err(_) => return Err(parse_error(line, format!("'{}' is not a valid {} index", text, what)))
+    };
+
+    let resolved = if i < 0 { len as i64 + i } else { i - 1 };
+    if i == 0 || resolved < 0 || resolved >= len as i64 {
+        return Err(parse_error(line, format!("{} index {} is out of range, there are {}", what, i, len)));
+    }
+
+    return Ok(resolved as usize)
+}
+
+//One corner of a face, as indices into the vertices, texture_coords and normals
+type FaceVertex = (usize, Option<usize>, Option<usize>);
+
+//Each corner of a face is v, v/vt, v//vn or v/vt/vn
+fn parse_face_vertex(line: usize, text: &str, obj: &ObjFile) -> Result<FaceVertex, ObjError> {
+    let parts: Vec<&str> = text.split('/').collect();
+    if parts.len() > 3 {
+        return Err(parse_error(line, format!("'{}' is not a valid face vertex", text)));
+    }
+
+    let v = parse_index(line, parts[0], obj.vertices.len(), "Vertex")?;
+
+    let vt =
+        if parts.len() > 1 && !parts[1].is_empty() {
+            Some(parse_index(line, parts[1], obj.texture_coords.len(), "Texture coordinate")?)
+        } else {
+            None
+        };
+
+    let vn =
+        if parts.len() > 2 && !parts[2].is_empty() {
+            Some(parse_index(line, parts[2], obj.normals.len(), "Normal")?)
+        } else {
+            None
+        };
+
+    return Ok((v, vt, vn))
+}
+
+fn add_face(line: usize, args: &[&str], group: usize, obj: &mut ObjFile) -> Result<(), ObjError> {
+    if args.len() < 3 {
+        return Err(parse_error(line, format!("A face needs at least 3 vertices, found {}", args.len())));
+    }
+
+    let mut corners = vec![];
+    for a in args {
+        corners.push(parse_face_vertex(line, a, obj)?);
+    }
+
+    let group = &mut obj.groups[group];
+    for i in 1..(corners.len() - 1) {
+        let (v1, t1, n1) = corners[0];
+        let (v2, t2, n2) = corners[i];
+        let (v3, t3, n3) = corners[i + 1];
+        let (p1, p2, p3) = (obj.vertices[v1], obj.vertices[v2], obj.vertices[v3]);
+
+        let texture_coords = match (t1, t2, t3) {
+            (Some(t1), Some(t2), Some(t3)) =>
+                Some([obj.texture_coords[t1], obj.texture_coords[t2], obj.texture_coords[t3]]),
+            _ => None
+        };
+
+        match (n1, n2, n3) {
+            (Some(n1), Some(n2), Some(n3)) => group.smooth_triangles.push(triangle::SmoothTriangle {
+                texture_coords: texture_coords,
+                ..triangle::new_smooth(p1, p2, p3, obj.normals[n1], obj.normals[n2], obj.normals[n3])
+            }),
+            _ => group.triangles.push(triangle::Triangle {
+                texture_coords: texture_coords,
+                ..triangle::new(p1, p2, p3)
+            })
+        }
+    }
+
+    return Ok(())
+}
+
+pub fn parse(text: &str) -> Result<ObjFile, ObjError> {
+    let mut obj = ObjFile {
+        vertices: vec![],
+        normals: vec![],
+        texture_coords: vec![],
+        groups: vec![new_group("")],
+        ignored: vec![]
+    };
+    let mut current = 0;
+
+    for (index, raw_line) in text.lines().enumerate() {
+        let line = index + 1;
+
+        //Everything after a # is a comment
+        let content = match raw_line.find('#') {
+            Some(i) => &raw_line[..i],
+            None => raw_line
+        };
+
+        let words: Vec<&str> = content.split_whitespace().collect();
+        if words.is_empty() {
+            continue;
+        }
+
+        let args = &words[1..];
+        match words[0] {
+            "v" => {
+                let n = parse_numbers(line, args, 3)?;
+                obj.vertices.push(primatives::point(n[0], n[1], n[2]));
+            },
+            "vn" => {
+                let n = parse_numbers(line, args, 3)?;
+                let normal = primatives::vec3(n[0], n[1], n[2]);
+
+                //A zero length normal has no direction, and can not be normalized
+                let length = normal.magnitude();
+                if length == 0.0 || !length.is_finite() {
+                    return Err(parse_error(line, format!("Normal ({} {} {}) has no direction", n[0], n[1], n[2])));
+                }
+                obj.normals.push(normal.normalized());
+            },
+            "vt" => {
+                let n = parse_numbers(line, args, 1)?;
+                obj.texture_coords.push((n[0], if n.len() > 1 { n[1] } else { 0.0 }));
+            },
+            "f" => add_face(line, args, current, &mut obj)?,
+            "g" | "o" => {
+                let name = args.join(" ");
+                current = match obj.groups.iter().position(|g| g.name == name) {
+                    Some(i) => i,
+                    None => {
+                        obj.groups.push(new_group(&name));
+                        obj.groups.len() - 1
+                    }
+                };
+            },
+            _ => obj.ignored.push(IgnoredLine {
+                line: line,
+                text: raw_line.to_string()
+            })
+        }
+    }
+
+    //Drop the unnamed group if everything was in named groups
+    if obj.groups.len() > 1 && obj.groups[0].triangles.is_empty() && obj.groups[0].smooth_triangles.is_empty() {
+        obj.groups.remove(0);
+    }
+
+    return Ok(obj)
+}
+
+impl ObjGroup {
+    pub fn shapes(&self) -> Vec<&dyn Shape> {
+        let mut ret_val: Vec<&dyn Shape> = vec![];
+        for t in &self.triangles {
+            ret_val.push(t);
+        }
+        for t in &self.smooth_triangles {
+            ret_val.push(t);
+        }
+
+        return ret_val
+    }
+}
+
+impl ObjFile {
+    pub fn group(&self, name: &str) -> Option<&ObjGroup> {
+        return self.groups.iter().find(|g| g.name == name);
+    }
+
+    //Every triangle in every group, ready to be added to World::objects
+    pub fn shapes(&self) -> Vec<&dyn Shape> {
+        let mut ret_val = vec![];
+        for g in &self.groups {
+            ret_val.extend(g.shapes());
+        }
+
+        return ret_val
+    }
+}
@@ -0,0 +1,51 @@
+use std::ops;
+use std::sync::Arc;
+
+use crate::primatives;
+use crate::matrix;
+use crate::color;
+use crate::patterns::{Pattern};
+
+/* Averages two patterns together. Two stripe patterns at right angles
+ * to each other blend into a plaid
+ */
+#[derive(Debug, Clone)]
+pub struct Blend {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub transformation: matrix::Matrix4x4
+}
+
+pub fn new(a: impl Into<Arc<dyn Pattern>>, b: impl Into<Arc<dyn Pattern>>) -> Blend {
+    return Blend {
+        a: a.into(),
+        b: b.into(),
+        transformation: matrix::IDENTITY_MATRIX_4X4
+    }
+}
+
+impl ops::Mul<Blend> for matrix::Matrix4x4 {
+    type Output = Blend;
+    fn mul(self, b: Blend) -> Blend {
+        return Blend {
+            transformation: b.transformation * self,
+            ..b
+        }
+    }
+}
+
+impl Pattern for Blend {
+    fn local_color_at(&self, p: primatives::PointT) -> color::Color {
+        return (self.a.color_at(p) + self.b.color_at(p)) * 0.5;
+    }
+
+    fn transformation(&self) -> matrix::Matrix4x4 {
+        return self.transformation;
+    }
+}
+
+impl From<Blend> for Arc<dyn Pattern> {
+    fn from(b: Blend) -> Arc<dyn Pattern> {
+        return Arc::new(b);
+    }
+}
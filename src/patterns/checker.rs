@@ -0,0 +1,59 @@
+use std::ops;
+use std::sync::Arc;
+
+use crate::primatives;
+use crate::matrix;
+use crate::color;
+use crate::patterns::{Pattern};
+
+/* Unit cubes of a and b, alternating on all three axes. On a plane
+ * this is a checkerboard, and on other shapes, the board is carved
+ * into the shape like a block of wood
+ */
+#[derive(Debug, Clone)]
+pub struct Checker {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub transformation: matrix::Matrix4x4
+}
+
+pub fn new(a: impl Into<Arc<dyn Pattern>>, b: impl Into<Arc<dyn Pattern>>) -> Checker {
+    return Checker {
+        a: a.into(),
+        b: b.into(),
+        transformation: matrix::IDENTITY_MATRIX_4X4
+    }
+}
+
+impl ops::Mul<Checker> for matrix::Matrix4x4 {
+    type Output = Checker;
+    fn mul(self, c: Checker) -> Checker {
+        return Checker {
+            transformation: c.transformation * self,
+            ..c
+        }
+    }
+}
+
+impl Pattern for Checker {
+    fn local_color_at(&self, p: primatives::PointT) -> color::Color {
+        let sum = p.x.floor() + p.y.floor() + p.z.floor();
+
+        return
+            if sum.rem_euclid(2.0) == 0.0 {
+                self.a.color_at(p)
+            } else {
+                self.b.color_at(p)
+            };
+    }
+
+    fn transformation(&self) -> matrix::Matrix4x4 {
+        return self.transformation;
+    }
+}
+
+impl From<Checker> for Arc<dyn Pattern> {
+    fn from(c: Checker) -> Arc<dyn Pattern> {
+        return Arc::new(c);
+    }
+}
@@ -0,0 +1,55 @@
+use std::ops;
+use std::sync::Arc;
+
+use crate::primatives;
+use crate::matrix;
+use crate::color;
+use crate::patterns::{Pattern};
+
+/* Fades from a to b going from x = 0 to x = 1, then jumps back to a
+ * and starts over
+ */
+#[derive(Debug, Clone)]
+pub struct Gradient {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub transformation: matrix::Matrix4x4
+}
+
+pub fn new(a: impl Into<Arc<dyn Pattern>>, b: impl Into<Arc<dyn Pattern>>) -> Gradient {
+    return Gradient {
+        a: a.into(),
+        b: b.into(),
+        transformation: matrix::IDENTITY_MATRIX_4X4
+    }
+}
+
+impl ops::Mul<Gradient> for matrix::Matrix4x4 {
+    type Output = Gradient;
+    fn mul(self, g: Gradient) -> Gradient {
+        return Gradient {
+            transformation: g.transformation * self,
+            ..g
+        }
+    }
+}
+
+impl Pattern for Gradient {
+    fn local_color_at(&self, p: primatives::PointT) -> color::Color {
+        let a = self.a.color_at(p);
+        let b = self.b.color_at(p);
+        let fraction = p.x - p.x.floor();
+
+        return a + (b - a) * fraction;
+    }
+
+    fn transformation(&self) -> matrix::Matrix4x4 {
+        return self.transformation;
+    }
+}
+
+impl From<Gradient> for Arc<dyn Pattern> {
+    fn from(g: Gradient) -> Arc<dyn Pattern> {
+        return Arc::new(g);
+    }
+}
@@ -0,0 +1,67 @@
+use std::fmt;
+
+use crate::canvas;
+use crate::ppm;
+use crate::color;
+use crate::patterns::uv;
+
+/* A picture, used as a flat pattern. (0, 0) is the bottom left corner of
+ * the picture and (1, 1) the top right. Colors are not blended between
+ * pixels, the nearest one is used
+ */
+#[derive(Debug, Clone)]
+pub struct ImageTexture {
+    pub canvas: canvas::Canvas
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImageError {
+    //There are no pixels to look colors up in
+    Empty {
+        width: usize,
+        height: usize
+    },
+
+    //The picture could not be loaded
+    Ppm(ppm::PpmError)
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            ImageError::Empty { width, height } => write!(f, "Image texture is empty ({} x {})", width, height),
+            ImageError::Ppm(e) => write!(f, "{}", e)
+        }
+    }
+}
+
+pub fn new(c: canvas::Canvas) -> Result<ImageTexture, ImageError> {
+    if c.width == 0 || c.height == 0 {
+        return Err(ImageError::Empty {
+            width: c.width,
+            height: c.height
+        })
+    }
+
+    return Ok(ImageTexture {
+        canvas: c
+    })
+}
+
+//Loads the texture from a PPM file, see ppm::load
+pub fn load(filename: &str) -> Result<ImageTexture, ImageError> {
+    return match ppm::load(filename) {
+        Ok(c) => new(c),
+        Err(e) => Err(ImageError::Ppm(e))
+    }
+}
+
+impl uv::UvPattern for ImageTexture {
+    fn uv_color_at(&self, u: f64, v: f64) -> color::Color {
+        //Canvas rows go top to bottom, so v is flipped
+        let x = (u.clamp(0.0, 1.0) * (self.canvas.width - 1) as f64).round() as usize;
+        let y = ((1.0 - v.clamp(0.0, 1.0)) * (self.canvas.height - 1) as f64).round() as usize;
+
+        return self.canvas.contents[(y * self.canvas.width) + x];
+    }
+}
@@ -0,0 +1,64 @@
+pub mod stripe;
+pub mod gradient;
+pub mod ring;
+pub mod checker;
+pub mod blend;
+pub mod perturbed;
+pub mod uv;
+pub mod image;
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::primatives;
+use crate::matrix;
+use crate::color;
+
+/* A pattern gives a color for every point in space. Patterns have their
+ * own transformation on top of the object they are painted on, so a
+ * stripe can be made thinner or turned without touching the object.
+ * Like shapes, patterns are shared between render threads
+ */
+pub trait Pattern: Sync + Send + fmt::Debug {
+    //The color at a point in the pattern's own space
+    fn local_color_at(&self, p: primatives::PointT) -> color::Color;
+    fn transformation(&self) -> matrix::Matrix4x4;
+
+    /* The color at a point in the space of whatever the pattern is on,
+     * either an object, or another pattern it is nested inside of
+     */
+    fn color_at(&self, p: primatives::PointT) -> color::Color {
+        return self.local_color_at(self.transformation().inverse() * p);
+    }
+}
+
+//The same color everywhere. Lets plain colors be used where a pattern is expected
+#[derive(Debug, Copy, Clone)]
+pub struct Solid {
+    pub color: color::Color
+}
+
+pub fn solid(c: color::Color) -> Solid {
+    return Solid {
+        color: c
+    }
+}
+
+impl Pattern for Solid {
+    fn local_color_at(&self, _p: primatives::PointT) -> color::Color {
+        return self.color;
+    }
+
+    fn transformation(&self) -> matrix::Matrix4x4 {
+        return matrix::IDENTITY_MATRIX_4X4;
+    }
+}
+
+/* Patterns are built out of other patterns (eg, stripes of checkers), so
+ * anywhere a pattern can go, a plain color can go too
+ */
+impl From<color::Color> for Arc<dyn Pattern> {
+    fn from(c: color::Color) -> Arc<dyn Pattern> {
+        return Arc::new(solid(c));
+    }
+}
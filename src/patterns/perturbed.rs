@@ -0,0 +1,71 @@
+use std::ops;
+use std::sync::Arc;
+
+use crate::primatives;
+use crate::matrix;
+use crate::color;
+use crate::noise;
+use crate::patterns::{Pattern};
+
+/* Nudges each point by a little bit of noise before looking it up in
+ * another pattern, so straight lines come out wavy. Perturbed stripes
+ * look like marble, and perturbed rings like wood grain. scale is the
+ * furthest (roughly) a point is moved, and more octaves give finer,
+ * rougher wobbles
+ */
+#[derive(Debug, Clone)]
+pub struct Perturbed {
+    pub pattern: Arc<dyn Pattern>,
+    pub scale: f64,
+    pub octaves: usize,
+    pub transformation: matrix::Matrix4x4
+}
+
+pub fn new(pattern: impl Into<Arc<dyn Pattern>>, scale: f64, octaves: usize) -> Perturbed {
+    return Perturbed {
+        pattern: pattern.into(),
+        scale: scale,
+        octaves: octaves,
+        transformation: matrix::IDENTITY_MATRIX_4X4
+    }
+}
+
+impl ops::Mul<Perturbed> for matrix::Matrix4x4 {
+    type Output = Perturbed;
+    fn mul(self, p: Perturbed) -> Perturbed {
+        return Perturbed {
+            transformation: p.transformation * self,
+            ..p
+        }
+    }
+}
+
+impl Pattern for Perturbed {
+    fn local_color_at(&self, p: primatives::PointT) -> color::Color {
+        /* Each axis is nudged by a different noise value, looked up far
+         * enough away from p that the three do not move together
+         */
+        let offset = |dx: f64, dy: f64, dz: f64| -> f64 {
+            let q = primatives::point(p.x + dx, p.y + dy, p.z + dz);
+            return noise::fractal(q, self.octaves, 0.5) * self.scale;
+        };
+
+        let jittered = primatives::point(
+            p.x + offset(0.0, 0.0, 0.0),
+            p.y + offset(31.4, 15.9, 26.5),
+            p.z + offset(-27.1, 82.8, -18.2)
+        );
+
+        return self.pattern.color_at(jittered);
+    }
+
+    fn transformation(&self) -> matrix::Matrix4x4 {
+        return self.transformation;
+    }
+}
+
+impl From<Perturbed> for Arc<dyn Pattern> {
+    fn from(p: Perturbed) -> Arc<dyn Pattern> {
+        return Arc::new(p);
+    }
+}
@@ -0,0 +1,56 @@
+use std::ops;
+use std::sync::Arc;
+
+use crate::primatives;
+use crate::matrix;
+use crate::color;
+use crate::patterns::{Pattern};
+
+//Rings of a and b, one unit wide, around the y axis. Like a bullseye
+#[derive(Debug, Clone)]
+pub struct Ring {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub transformation: matrix::Matrix4x4
+}
+
+pub fn new(a: impl Into<Arc<dyn Pattern>>, b: impl Into<Arc<dyn Pattern>>) -> Ring {
+    return Ring {
+        a: a.into(),
+        b: b.into(),
+        transformation: matrix::IDENTITY_MATRIX_4X4
+    }
+}
+
+impl ops::Mul<Ring> for matrix::Matrix4x4 {
+    type Output = Ring;
+    fn mul(self, r: Ring) -> Ring {
+        return Ring {
+            transformation: r.transformation * self,
+            ..r
+        }
+    }
+}
+
+impl Pattern for Ring {
+    fn local_color_at(&self, p: primatives::PointT) -> color::Color {
+        let distance = (p.x * p.x + p.z * p.z).sqrt();
+
+        return
+            if distance.floor().rem_euclid(2.0) == 0.0 {
+                self.a.color_at(p)
+            } else {
+                self.b.color_at(p)
+            };
+    }
+
+    fn transformation(&self) -> matrix::Matrix4x4 {
+        return self.transformation;
+    }
+}
+
+impl From<Ring> for Arc<dyn Pattern> {
+    fn from(r: Ring) -> Arc<dyn Pattern> {
+        return Arc::new(r);
+    }
+}
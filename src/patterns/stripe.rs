@@ -0,0 +1,54 @@
+use std::ops;
+use std::sync::Arc;
+
+use crate::primatives;
+use crate::matrix;
+use crate::color;
+use crate::patterns::{Pattern};
+
+//Alternates between a and b every unit along the x axis
+#[derive(Debug, Clone)]
+pub struct Stripe {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub transformation: matrix::Matrix4x4
+}
+
+pub fn new(a: impl Into<Arc<dyn Pattern>>, b: impl Into<Arc<dyn Pattern>>) -> Stripe {
+    return Stripe {
+        a: a.into(),
+        b: b.into(),
+        transformation: matrix::IDENTITY_MATRIX_4X4
+    }
+}
+
+impl ops::Mul<Stripe> for matrix::Matrix4x4 {
+    type Output = Stripe;
+    fn mul(self, s: Stripe) -> Stripe {
+        return Stripe {
+            transformation: s.transformation * self,
+            ..s
+        }
+    }
+}
+
+impl Pattern for Stripe {
+    fn local_color_at(&self, p: primatives::PointT) -> color::Color {
+        return
+            if p.x.floor().rem_euclid(2.0) == 0.0 {
+                self.a.color_at(p)
+            } else {
+                self.b.color_at(p)
+            };
+    }
+
+    fn transformation(&self) -> matrix::Matrix4x4 {
+        return self.transformation;
+    }
+}
+
+impl From<Stripe> for Arc<dyn Pattern> {
+    fn from(s: Stripe) -> Arc<dyn Pattern> {
+        return Arc::new(s);
+    }
+}
@@ -0,0 +1,255 @@
+use std::fmt;
+use std::ops;
+use std::sync::Arc;
+
+use crate::primatives;
+use crate::matrix;
+use crate::color;
+use crate::patterns::{Pattern};
+
+use std::f64::consts::PI;
+
+/* A flat, two dimensional pattern, like a picture. u goes from 0 at the
+ * left edge to 1 at the right, and v from 0 at the bottom to 1 at the top.
+ * A mapping wraps it around a three dimensional shape
+ */
+pub trait UvPattern: Sync + Send + fmt::Debug {
+    fn uv_color_at(&self, u: f64, v: f64) -> color::Color;
+}
+
+//A checkerboard of width x height squares
+#[derive(Debug, Copy, Clone)]
+pub struct UvChecker {
+    pub width: f64,
+    pub height: f64,
+    pub a: color::Color,
+    pub b: color::Color
+}
+
+pub fn new_checker(w: f64, h: f64, a: color::Color, b: color::Color) -> UvChecker {
+    return UvChecker {
+        width: w,
+        height: h,
+        a: a,
+        b: b
+    }
+}
+
+impl UvPattern for UvChecker {
+    fn uv_color_at(&self, u: f64, v: f64) -> color::Color {
+        let sum = (u * self.width).floor() + (v * self.height).floor();
+
+        return
+            if sum.rem_euclid(2.0) == 0.0 {
+                self.a
+            } else {
+                self.b
+            };
+    }
+}
+
+/* Ways of turning a point on a shape into (u, v). Spherical wraps the
+ * picture around the origin like a map of the earth, planar tiles it
+ * across the xz plane, cylindrical wraps it around the y axis, repeating
+ * every unit up, and cube unfolds it onto whichever face of a -1 to 1
+ * cube the point is nearest
+ */
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Mapping {
+    Spherical,
+    Planar,
+    Cylindrical,
+    Cube
+}
+
+impl Mapping {
+    pub fn uv_at(&self, p: primatives::PointT) -> (f64, f64) {
+        return match self {
+            Mapping::Spherical => spherical_map(p),
+            Mapping::Planar => planar_map(p),
+            Mapping::Cylindrical => cylindrical_map(p),
+            Mapping::Cube => cube_map(p)
+        };
+    }
+}
+
+//Angle around the y axis, turned into u. Shared by spheres and cylinders
+fn azimuth_u(p: primatives::PointT) -> f64 {
+    let theta = p.x.atan2(p.z);
+    let raw_u = theta / (2.0 * PI);
+
+    //raw_u runs from -0.5 to 0.5, and backwards as seen from outside
+    return 1.0 - (raw_u + 0.5);
+}
+
+pub fn spherical_map(p: primatives::PointT) -> (f64, f64) {
+    let radius = primatives::vec3(p.x, p.y, p.z).magnitude();
+    let phi = (p.y / radius).acos();
+
+    //The north pole is v = 1
+    return (azimuth_u(p), 1.0 - phi / PI);
+}
+
+pub fn planar_map(p: primatives::PointT) -> (f64, f64) {
+    return (p.x.rem_euclid(1.0), p.z.rem_euclid(1.0));
+}
+
+pub fn cylindrical_map(p: primatives::PointT) -> (f64, f64) {
+    return (azimuth_u(p), p.y.rem_euclid(1.0));
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CubeFace {
+    Left,
+    Right,
+    Front,
+    Back,
+    Up,
+    Down
+}
+
+//The face of the cube a point is on, ie, its largest coordinate
+pub fn cube_face(p: primatives::PointT) -> CubeFace {
+    let largest = p.x.abs().max(p.y.abs()).max(p.z.abs());
+
+    return
+        if largest == p.x {
+            CubeFace::Right
+        } else if largest == -p.x {
+            CubeFace::Left
+        } else if largest == p.y {
+            CubeFace::Up
+        } else if largest == -p.y {
+            CubeFace::Down
+        } else if largest == p.z {
+            CubeFace::Front
+        } else {
+            CubeFace::Back
+        };
+}
+
+/* (u, v) on one face of the cube, as seen from outside of the cube with
+ * the up face above the front, left, right and back faces
+ */
+pub fn cube_face_uv(face: CubeFace, p: primatives::PointT) -> (f64, f64) {
+    let (u, v) = match face {
+        CubeFace::Front => (p.x + 1.0, p.y + 1.0),
+        CubeFace::Back => (1.0 - p.x, p.y + 1.0),
+        CubeFace::Left => (p.z + 1.0, p.y + 1.0),
+        CubeFace::Right => (1.0 - p.z, p.y + 1.0),
+        CubeFace::Up => (p.x + 1.0, 1.0 - p.z),
+        CubeFace::Down => (p.x + 1.0, p.z + 1.0)
+    };
+
+    return (u.rem_euclid(2.0) / 2.0, v.rem_euclid(2.0) / 2.0);
+}
+
+pub fn cube_map(p: primatives::PointT) -> (f64, f64) {
+    return cube_face_uv(cube_face(p), p);
+}
+
+//Wraps a flat pattern around an object using one of the mappings
+#[derive(Debug, Clone)]
+pub struct TextureMap {
+    pub texture: Arc<dyn UvPattern>,
+    pub mapping: Mapping,
+    pub transformation: matrix::Matrix4x4
+}
+
+pub fn new(texture: Arc<dyn UvPattern>, mapping: Mapping) -> TextureMap {
+    return TextureMap {
+        texture: texture,
+        mapping: mapping,
+        transformation: matrix::IDENTITY_MATRIX_4X4
+    }
+}
+
+impl ops::Mul<TextureMap> for matrix::Matrix4x4 {
+    type Output = TextureMap;
+    fn mul(self, t: TextureMap) -> TextureMap {
+        return TextureMap {
+            transformation: t.transformation * self,
+            ..t
+        }
+    }
+}
+
+impl Pattern for TextureMap {
+    fn local_color_at(&self, p: primatives::PointT) -> color::Color {
+        let (u, v) = self.mapping.uv_at(p);
+        return self.texture.uv_color_at(u, v);
+    }
+
+    fn transformation(&self) -> matrix::Matrix4x4 {
+        return self.transformation;
+    }
+}
+
+impl From<TextureMap> for Arc<dyn Pattern> {
+    fn from(t: TextureMap) -> Arc<dyn Pattern> {
+        return Arc::new(t);
+    }
+}
+
+//A cube mapping with a different picture on every face, eg, a skybox
+#[derive(Debug, Clone)]
+pub struct CubeMap {
+    pub left: Arc<dyn UvPattern>,
+    pub right: Arc<dyn UvPattern>,
+    pub front: Arc<dyn UvPattern>,
+    pub back: Arc<dyn UvPattern>,
+    pub up: Arc<dyn UvPattern>,
+    pub down: Arc<dyn UvPattern>,
+    pub transformation: matrix::Matrix4x4
+}
+
+pub fn new_cube_map(left: Arc<dyn UvPattern>, right: Arc<dyn UvPattern>, front: Arc<dyn UvPattern>,
+back: Arc<dyn UvPattern>, up: Arc<dyn UvPattern>, down: Arc<dyn UvPattern>) -> CubeMap {
+    return CubeMap {
+        left: left,
+        right: right,
+        front: front,
+        back: back,
+        up: up,
+        down: down,
+        transformation: matrix::IDENTITY_MATRIX_4X4
+    }
+}
+
+impl ops::Mul<CubeMap> for matrix::Matrix4x4 {
+    type Output = CubeMap;
+    fn mul(self, c: CubeMap) -> CubeMap {
+        return CubeMap {
+            transformation: c.transformation * self,
+            ..c
+        }
+    }
+}
+
+impl Pattern for CubeMap {
+    fn local_color_at(&self, p: primatives::PointT) -> color::Color {
+        let face = cube_face(p);
+        let (u, v) = cube_face_uv(face, p);
+
+        let texture = match face {
+            CubeFace::Left => &self.left,
+            CubeFace::Right => &self.right,
+            CubeFace::Front => &self.front,
+            CubeFace::Back => &self.back,
+            CubeFace::Up => &self.up,
+            CubeFace::Down => &self.down
+        };
+
+        return texture.uv_color_at(u, v);
+    }
+
+    fn transformation(&self) -> matrix::Matrix4x4 {
+        return self.transformation;
+    }
+}
+
+impl From<CubeMap> for Arc<dyn Pattern> {
+    fn from(c: CubeMap) -> Arc<dyn Pattern> {
+        return Arc::new(c);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::zlib;
+
+/* Encodes 8 bit RGB pixels as a PNG file. Each row is run through
+ * whichever PNG filter makes it look the most like zeros (the usual
+ * rule of thumb), and the filtered rows are zlib compressed into a
+ * single IDAT chunk
+ */
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+const BYTES_PER_PIXEL: usize = 3;
+
+//The table for the CRC-32 in every chunk, built when compiling
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 == 1 { 0xEDB88320 ^ (c >> 1) } else { c >> 1 };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+};
+
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut c = 0xFFFFFFFF;
+    for byte in data {
+        c = CRC_TABLE[((c ^ *byte as u32) & 0xFF) as usize] ^ (c >> 8);
+    }
+
+    return c ^ 0xFFFFFFFF
+}
+
+//Length, type, data, and a CRC of the type and data
+fn write_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+
+    let start = out.len();
+    out.extend(kind);
+    out.extend(data);
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let pa = (p - a as i16).abs();
+    let pb = (p - b as i16).abs();
+    let pc = (p - c as i16).abs();
+
+    return
+        if pa <= pb && pa <= pc {
+            a
+        } else if pb <= pc {
+            b
+        } else {
+            c
+        };
+}
+
+/* Filter type f applied to row, given the row above it (all zeros for
+ * the first row). a is the byte to the left, b above, and c above left
+ */
+fn filter_row(f: u8, row: &[u8], above: &[u8]) -> Vec<u8> {
+    let mut ret_val = Vec::with_capacity(row.len() + 1);
+    ret_val.push(f);
+
+    for i in 0..row.len() {
+        let a = if i >= BYTES_PER_PIXEL { row[i - BYTES_PER_PIXEL] } else { 0 };
+        let b = above[i];
+        let c = if i >= BYTES_PER_PIXEL { above[i - BYTES_PER_PIXEL] } else { 0 };
+
+        let predicted = match f {
+            0 => 0,
+            1 => a,
+            2 => b,
+            3 => ((a as u16 + b as u16) / 2) as u8,
+            _ => paeth(a, b, c)
+        };
+        ret_val.push(row[i].wrapping_sub(predicted));
+    }
+
+    return ret_val
+}
+
+//Bytes near 0 (or 256) are the most likely to repeat
+fn filter_cost(filtered: &[u8]) -> u64 {
+    return filtered[1..].iter().map(|b| (*b as i8).unsigned_abs() as u64).sum();
+}
+
+//rgb is width * height pixels, 3 bytes each, top row first
+pub fn encode(width: usize, height: usize, rgb: &[u8]) -> Vec<u8> {
+    if rgb.len() != width * height * BYTES_PER_PIXEL {
+        panic!("Expected {} bytes of pixels, got {}", width * height * BYTES_PER_PIXEL, rgb.len());
+    }
+
+    let stride = width * BYTES_PER_PIXEL;
+    let zeros = vec![0; stride];
+    let mut filtered = Vec::with_capacity((stride + 1) * height);
+
+    for y in 0..height {
+        let row = &rgb[(y * stride)..((y + 1) * stride)];
+        let above = if y == 0 { &zeros[..] } else { &rgb[((y - 1) * stride)..(y * stride)] };
+
+        let best = (0..5)
+            .map(|f| filter_row(f, row, above))
+            .min_by_key(|r| filter_cost(r))
+            .unwrap();
+        filtered.extend(best);
+    }
+
+    let mut header = vec![];
+    header.extend((width as u32).to_be_bytes());
+    header.extend((height as u32).to_be_bytes());
+
+    //8 bits per channel, RGB, deflate, adaptive filtering, not interlaced
+    header.extend([8, 2, 0, 0, 0]);
+
+    let mut ret_val = SIGNATURE.to_vec();
+    write_chunk(&mut ret_val, b"IHDR", &header);
+    write_chunk(&mut ret_val, b"IDAT", &zlib::compress(&filtered));
+    write_chunk(&mut ret_val, b"IEND", &[]);
+
+    return ret_val
+}
@@ -0,0 +1,191 @@
+use std::fmt;
+use std::fs;
+
+use crate::canvas;
+use crate::color;
+
+/* Reads PPM images, both plain (P3) and binary (P6), into a canvas.
+ * Anything the netpbm tools would accept should work: comments (a # to
+ * the end of the line) anywhere in the header, any mix of spaces, tabs
+ * and line endings between numbers, and a maximum value anywhere from 1
+ * to 65535. Binary files with a maximum value over 255 use two bytes per
+ * number, highest byte first. Colors are scaled into 0.0 to 1.0
+ */
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PpmError {
+    //The file could not be read at all
+    Io(String),
+
+    //The file is not a valid PPM. offset is how many bytes in the problem is
+    Parse {
+        offset: usize,
+        message: String
+    }
+}
+
+impl fmt::Display for PpmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            PpmError::Io(message) => write!(f, "Could not read PPM file: {}", message),
+            PpmError::Parse { offset, message } => write!(f, "PPM byte {}: {}", offset, message)
+        }
+    }
+}
+
+pub fn load(filename: &str) -> Result<canvas::Canvas, PpmError> {
+    return match fs::read(filename) {
+        Ok(bytes) => parse(&bytes),
+        Err(e) => Err(PpmError::Io(format!("{}: {}", filename, e)))
+    }
+}
+
+fn parse_error(offset: usize, message: String) -> PpmError {
+    return PpmError::Parse {
+        offset: offset,
+        message: message
+    }
+}
+
+//Walks through the file, a whitespace separated number at a time
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize
+}
+
+impl Reader<'_> {
+    fn skip_whitespace_and_comments(&mut self) {
+        while self.position < self.bytes.len() {
+            let b = self.bytes[self.position];
+            if b == b'#' {
+                while self.position < self.bytes.len() && self.bytes[self.position] != b'\n' && self.bytes[self.position] != b'\r' {
+                    self.position += 1;
+                }
+            } else if b.is_ascii_whitespace() {
+                self.position += 1;
+            } else {
+                return;
+            }
+        }
+    }
+
+    fn token(&mut self, what: &str) -> Result<&[u8], PpmError> {
+        self.skip_whitespace_and_comments();
+
+        let start = self.position;
+        while self.position < self.bytes.len() && !self.bytes[self.position].is_ascii_whitespace() && self.bytes[self.position] != b'#' {
+            self.position += 1;
+        }
+
+        if start == self.position {
+            return Err(parse_error(start, format!("File ended early, expected {}", what)));
+        }
+
+        return Ok(&self.bytes[start..self.position])
+    }
+
+    fn number(&mut self, what: &str) -> Result<u32, PpmError> {
+        let start = self.position;
+        let t = self.token(what)?;
+
+        return match std::str::from_utf8(t).ok().and_then(|s| s.parse::<u32>().ok()) {
+            Some(n) => Ok(n),
+            None => Err(parse_error(start, format!("Invalid {} '{}'", what, String::from_utf8_lossy(t))))
+        }
+    }
+
+    //One binary sample, one or two bytes wide
+    fn binary(&mut self, wide: bool) -> Result<u32, PpmError> {
+        let width = if wide { 2 } else { 1 };
+        if self.position + width > self.bytes.len() {
+            return Err(parse_error(self.position, "File ended early, expected more pixels".to_string()));
+        }
+
+        let value =
+            if wide {
+                ((self.bytes[self.position] as u32) << 8) | (self.bytes[self.position + 1] as u32)
+            } else {
+                self.bytes[self.position] as u32
+            };
+        self.position += width;
+
+        return Ok(value)
+    }
+}
+
+pub fn parse(bytes: &[u8]) -> Result<canvas::Canvas, PpmError> {
+    let mut r = Reader {
+        bytes: bytes,
+        position: 0
+    };
+
+    let binary = match r.token("the P3 or P6 magic number")? {
+        b"P3" => false,
+        b"P6" => true,
+        other => return Err(parse_error(0, format!("Unsupported format '{}', expected P3 or P6", String::from_utf8_lossy(other))))
+    };
+
+    let width = r.number("width")? as usize;
+    let height = r.number("height")? as usize;
+    if width == 0 || height == 0 {
+        return Err(parse_error(r.position, format!("Image is empty ({} x {})", width, height)));
+    }
+
+    let maxval_offset = r.position;
+    let maxval = r.number("maximum value")?;
+    if maxval == 0 || maxval > 65535 {
+        return Err(parse_error(maxval_offset, format!("Maximum value must be between 1 and 65535, found {}", maxval)));
+    }
+
+    //Binary data starts after exactly one whitespace character
+    if binary {
+        if r.position >= bytes.len() || !bytes[r.position].is_ascii_whitespace() {
+            return Err(parse_error(r.position, "Expected whitespace before pixel data".to_string()));
+        }
+        r.position += 1;
+    }
+
+    /* Make sure the pixels are really there before making the canvas, so a
+     * bad header can not ask for more memory than the file could fill.
+     * Binary samples are one or two bytes each, plain ones are at least a
+     * digit and a space
+     */
+    let samples = match width.checked_mul(height).and_then(|n| n.checked_mul(3)) {
+        Some(n) => n,
+        None => return Err(parse_error(maxval_offset, format!("Image is too large ({} x {})", width, height)))
+    };
+
+    let remaining = bytes.len() - r.position;
+    let needed =
+        if binary {
+            samples.checked_mul(if maxval > 255 { 2 } else { 1 })
+        } else {
+            samples.checked_mul(2).map(|n| n - 1)
+        };
+    if needed.is_none_or(|n| n > remaining) {
+        return Err(parse_error(bytes.len(), "File ended early, expected more pixels".to_string()));
+    }
+
+    let mut ret_val = canvas::new(width, height);
+    for pixel in ret_val.contents.iter_mut() {
+        let mut channels = [0.0; 3];
+        for c in channels.iter_mut() {
+            let offset = r.position;
+            let value =
+                if binary {
+                    r.binary(maxval > 255)?
+                } else {
+                    r.number("color value")?
+                };
+
+            if value > maxval {
+                return Err(parse_error(offset, format!("Color value {} is over the maximum of {}", value, maxval)));
+            }
+            *c = value as f64 / maxval as f64;
+        }
+
+        *pixel = color::new(channels[0], channels[1], channels[2]);
+    }
+
+    return Ok(ret_val)
+}
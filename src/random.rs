@@ -0,0 +1,24 @@
+/* Stateless pseudo-random numbers. Rather than keeping a generator
+ * around, values are hashed out of whatever seeds the caller has on
+ * hand (a point being shaded, a sample index, ...). The same seeds
+ * always give the same value, so renders are repeatable.
+ */
+
+//SplitMix64 finalizer. Scrambles the bits of x so that nearby inputs give unrelated outputs
+pub fn hash(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    return z ^ (z >> 31)
+}
+
+//Returns a value in [0.0, 1.0) derived from all of the seeds
+pub fn jitter(seeds: &[u64]) -> f64 {
+    let mut h = 0;
+    for s in seeds {
+        h = hash(h ^ s);
+    }
+
+    //Keep the top 53 bits, which is all of the precision an f64 mantissa has
+    return (h >> 11) as f64 / (1u64 << 53) as f64
+}
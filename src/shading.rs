@@ -6,21 +6,31 @@ use crate::shapes::intersection;
 
 /* The Phong reflection model colors and object by adding together the
  * ambient room lighting, diffused light, and a specular highlight 
- * (reflection of light source)
+ * (reflection of light source). A point in shadow only receives the
+ * ambient light. object_point is loc in the space of the object being
+ * shaded, which is where the material's pattern (if any) is looked up.
  */
-pub fn shade(mtrl: material::Material, lt: light::Light, loc: primatives::PointT, 
-eyev: primatives::Vec3T, normalv: primatives::Vec3T) -> color::Color {
+pub fn shade(mtrl: &material::Material, lt: light::Light, loc: primatives::PointT, object_point: primatives::PointT,
+eyev: primatives::Vec3T, normalv: primatives::Vec3T, in_shadow: bool) -> color::Color {
 
 
-    //Calculate a vector from the light to the point being rendered
-    let lightv = (lt.location - loc).normalized();
+    //Calculate a vector from the point being rendered to the light
+    let lightv = lt.direction_from(loc);
 
     //The color of the item will be brighter or dimmer based on 
     //the intesity of the light in the room
-    let effecive_color = mtrl.color * lt.intensity;
+    let intensity = lt.intensity_at(loc);
+    let effecive_color = mtrl.color_at(object_point) * intensity;
 
     let ambient_color = effecive_color * mtrl.ambient;
 
+    if in_shadow {
+        return ambient_color;
+    }
+
+    //Distance only dims the light hitting the surface directly
+    let attenuation = lt.attenuation_at(loc);
+
     /* The dot product will represent the angle between the light vector
      * and the normal vector. If this angle is negative, the light is
      * on the other side of the surface, so no light will ever hit this
@@ -32,7 +42,7 @@ eyev: primatives::Vec3T, normalv: primatives::Vec3T) -> color::Color {
         if light_normal_dot_prod < 0.0 {
             (color::BLACK, color::BLACK)
         } else {
-            let diffuse = effecive_color * mtrl.diffuse * light_normal_dot_prod;
+            let diffuse = effecive_color * mtrl.diffuse * light_normal_dot_prod * attenuation;
 
             /* If the dot product of the reflected angle and the eye vector is
              * negative, then the specular highlight reflects away from the eye
@@ -45,7 +55,7 @@ eyev: primatives::Vec3T, normalv: primatives::Vec3T) -> color::Color {
                 } else {
                     //diffuse = effecive_color * mtrl.diffuse * reflect_eye_dot_prod;
                     let factor = reflect_eye_dot_prod.powf(mtrl.shininess);
-                    lt.intensity * mtrl.specular * factor
+                    intensity * mtrl.specular * factor * attenuation
                 };
 
             (diffuse, specular)
@@ -54,6 +64,28 @@ eyev: primatives::Vec3T, normalv: primatives::Vec3T) -> color::Color {
         return ambient_color + diffuse + specular;
 } 
 
-pub fn shade_intersection(i: intersection::Intersection, l: light::Light) -> color::Color {
-    return shade(i.shape.get_material(), l, i.location, i.eyev, i.normalv);
+pub fn shade_intersection(i: intersection::Intersection, l: light::Light, in_shadow: bool) -> color::Color {
+    return shade(&i.shape.get_material(), l, i.location, i.object_point, i.eyev, i.normalv, in_shadow);
+}
+
+/* Schlick's approximation of the Fresnel effect: the fraction of light
+ * that is reflected, rather than refracted, at a surface. Light hitting
+ * the surface at a shallow angle is mostly reflected.
+ */
+pub fn schlick(i: intersection::Intersection) -> f64 {
+    let mut cos = primatives::dot_product(i.eyev.normalized(), i.normalv);
+
+    //Total internal reflection can only happen going into a less dense material
+    if i.n1 > i.n2 {
+        let n = i.n1 / i.n2;
+        let sin2_t = n * n * (1.0 - cos * cos);
+        if sin2_t > 1.0 {
+            return 1.0
+        }
+
+        cos = (1.0 - sin2_t).sqrt();
+    }
+
+    let r0 = ((i.n1 - i.n2) / (i.n1 + i.n2)).powi(2);
+    return r0 + (1.0 - r0) * (1.0 - cos).powi(5)
 }
\ No newline at end of file
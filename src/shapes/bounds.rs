@@ -0,0 +1,150 @@
+use crate::primatives;
+use crate::matrix;
+use crate::ray;
+
+/* An axis aligned bounding box. Anything inside of the box
+ * can only be hit by a ray that also hits the box
+ */
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BoundingBox {
+    pub min: primatives::PointT,
+    pub max: primatives::PointT
+}
+
+pub fn new(min: primatives::PointT, max: primatives::PointT) -> BoundingBox {
+    min.check_type(primatives::TYPE_PNT);
+    max.check_type(primatives::TYPE_PNT);
+
+    return BoundingBox {
+        min: min,
+        max: max
+    }
+}
+
+//A box containing nothing. Adding anything to it gives a box around just that thing
+pub fn new_empty() -> BoundingBox {
+    let inf = f64::INFINITY;
+    return new(primatives::point(inf, inf, inf), primatives::point(-inf, -inf, -inf));
+}
+
+//A box containing everything, for shapes like planes that go on forever
+pub fn new_infinite() -> BoundingBox {
+    let inf = f64::INFINITY;
+    return new(primatives::point(-inf, -inf, -inf), primatives::point(inf, inf, inf));
+}
+
+//Returns the x, y or z (0, 1, 2) component of a tuple
+pub fn axis(t: primatives::Tuple, a: usize) -> f64 {
+    return match a {
+        0 => t.x,
+        1 => t.y,
+        2 => t.z,
+        _ => panic!("Invalid axis")
+    }
+}
+
+impl BoundingBox {
+    //True for boxes from new_empty() that nothing has been added to
+    pub fn is_empty(&self) -> bool {
+        return self.min.x > self.max.x || self.min.y > self.max.y || self.min.z > self.max.z
+    }
+
+    pub fn is_infinite(&self) -> bool {
+        if self.is_empty() {
+            return false
+        }
+
+        return !(self.min.x.is_finite() && self.min.y.is_finite() && self.min.z.is_finite() &&
+            self.max.x.is_finite() && self.max.y.is_finite() && self.max.z.is_finite())
+    }
+
+    pub fn add_point(&self, p: primatives::PointT) -> BoundingBox {
+        p.check_type(primatives::TYPE_PNT);
+
+        return new(
+            primatives::point(self.min.x.min(p.x), self.min.y.min(p.y), self.min.z.min(p.z)),
+            primatives::point(self.max.x.max(p.x), self.max.y.max(p.y), self.max.z.max(p.z))
+        )
+    }
+
+    pub fn merge(&self, b: BoundingBox) -> BoundingBox {
+        return self.add_point(b.min).add_point(b.max)
+    }
+
+    pub fn centroid(&self) -> primatives::PointT {
+        return primatives::point(
+            (self.min.x + self.max.x) / 2.0,
+            (self.min.y + self.max.y) / 2.0,
+            (self.min.z + self.max.z) / 2.0
+        )
+    }
+
+    pub fn surface_area(&self) -> f64 {
+        let d = self.max - self.min;
+        if d.x < 0.0 || d.y < 0.0 || d.z < 0.0 {
+            return 0.0
+        }
+
+        return 2.0 * (d.x * d.y + d.y * d.z + d.z * d.x)
+    }
+
+    /* Moves all eight corners of the box with the matrix, and returns
+     * the box around them. A rotated box is not axis aligned any more,
+     * so the result can be larger than the original
+     */
+    pub fn transformed(&self, m: matrix::Matrix4x4) -> BoundingBox {
+        //Infinity times zero is NaN, so an infinite box has to stay infinite
+        if self.is_infinite() {
+            return new_infinite();
+        } else if self.is_empty() {
+            return *self;
+        }
+
+        let mut ret_val = new_empty();
+        for x in [self.min.x, self.max.x] {
+            for y in [self.min.y, self.max.y] {
+                for z in [self.min.z, self.max.z] {
+                    ret_val = ret_val.add_point(m * primatives::point(x, y, z));
+                }
+            }
+        }
+
+        return ret_val
+    }
+
+    /* The slab method: along each axis, find the times the ray enters and
+     * leaves the space between the box's two faces. The ray is inside the
+     * box only where all three of those spans overlap. The whole line is
+     * tested, not just what is in front of the ray origin, because
+     * refraction needs to see the objects behind the ray too
+     */
+    pub fn intersects(&self, r: ray::Ray) -> bool {
+        if self.is_empty() {
+            return false
+        }
+
+        let mut t_min = f64::NEG_INFINITY;
+        let mut t_max = f64::INFINITY;
+
+        for a in 0..3 {
+            let origin = axis(r.origin, a);
+            let direction = axis(r.direction, a);
+            let (lower, upper) = (axis(self.min, a), axis(self.max, a));
+
+            //A ray parallel to the faces never crosses them
+            if direction == 0.0 {
+                if origin < lower || origin > upper {
+                    return false
+                }
+                continue;
+            }
+
+            let t1 = (lower - origin) / direction;
+            let t2 = (upper - origin) / direction;
+            t_min = t_min.max(t1.min(t2));
+            t_max = t_max.min(t1.max(t2));
+        }
+
+        return t_min <= t_max
+    }
+}
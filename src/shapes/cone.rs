@@ -0,0 +1,166 @@
+use std::ops;
+
+use crate::primatives;
+use crate::ray;
+use crate::matrix;
+use crate::material;
+use crate::shapes;
+use crate::shapes::intersection;
+use crate::shapes::bounds;
+
+//Margin of error for rays parallel to the walls, and for points on the caps
+const EPSILON: f64 = 0.0001;
+
+/* A double cone, with its tips touching at the origin, opening up and
+ * down the y axis. Its radius at any height is the absolute value of y.
+ * Like the cylinder, it can be cut off at `minimum` and `maximum`, and
+ * `closed` puts caps on the cut off ends
+ */
+#[derive(Debug, Clone)]
+pub struct Cone {
+    pub transformation: matrix::Matrix4x4,
+    pub material: material::Material,
+    pub minimum: f64,
+    pub maximum: f64,
+    pub closed: bool
+}
+
+pub fn new() -> Cone {
+    return Cone {
+        transformation: matrix::IDENTITY_MATRIX_4X4,
+        material: material::new_default(),
+        minimum: f64::NEG_INFINITY,
+        maximum: f64::INFINITY,
+        closed: false
+    }
+}
+
+impl ops::Mul<Cone> for matrix::Matrix4x4 {
+    type Output = Cone;
+    fn mul(self, c: Cone) -> Cone {
+        return Cone {
+            transformation: c.transformation * self,
+            ..c
+        }
+    }
+}
+
+impl Cone {
+    //Times at which the ray goes through the end caps
+    fn intersect_caps(&self, r: ray::Ray) -> Vec<f64> {
+        let mut ret_val = vec![];
+
+        if !self.closed || r.direction.y.abs() < EPSILON {
+            return ret_val
+        }
+
+        //The radius of each cap is the height it is cut off at
+        for cap in [self.minimum, self.maximum] {
+            let t = (cap - r.origin.y) / r.direction.y;
+            let x = r.origin.x + t * r.direction.x;
+            let z = r.origin.z + t * r.direction.z;
+
+            if (x * x) + (z * z) <= cap * cap {
+                ret_val.push(t);
+            }
+        }
+
+        return ret_val
+    }
+}
+
+impl shapes::Shape for Cone {
+
+    /* Every point on the wall satisfies x * x - y * y + z * z = 0, which
+     * gives a quadratic just like the cylinder's. Unlike the cylinder, a
+     * ray can be parallel to one half of the cone and still hit the other
+     * half, once. In that case, a is 0 and the quadratic becomes linear
+     */
+    fn intersect(&self, r_input: ray::Ray) -> Vec<intersection::Intersection<'_>> {
+        let r = self.transformation.inverse() * r_input;
+        let (o, dir) = (r.origin, r.direction);
+
+        let a = (dir.x * dir.x) - (dir.y * dir.y) + (dir.z * dir.z);
+        let b = 2.0 * ((o.x * dir.x) - (o.y * dir.y) + (o.z * dir.z));
+        let c = (o.x * o.x) - (o.y * o.y) + (o.z * o.z);
+
+        let wall_hits: Vec<f64> =
+            if a.abs() < EPSILON {
+                if b.abs() < EPSILON {
+                    vec![]
+                } else {
+                    vec![-c / (2.0 * b)]
+                }
+            } else {
+                let d = (b * b) - 4.0 * a * c;
+                if d < 0.0 {
+                    vec![]
+                } else {
+                    vec![(-b - d.sqrt()) / (2.0 * a), (-b + d.sqrt()) / (2.0 * a)]
+                }
+            };
+
+        let mut t_values = vec![];
+        for t in wall_hits {
+            let y = o.y + t * dir.y;
+            if self.minimum < y && y < self.maximum {
+                t_values.push(t);
+            }
+        }
+
+        t_values.extend(self.intersect_caps(r));
+
+        let mut ret_val = vec![];
+        for t in t_values {
+            ret_val.push(intersection::new(t, r_input.position(t), self, r_input));
+        }
+
+        return ret_val
+    }
+
+    fn world_to_object(&self, p: primatives::PointT) -> primatives::PointT {
+        return self.transformation.inverse() * p;
+    }
+
+    fn transformation(&self) -> matrix::Matrix4x4 {
+        return self.transformation;
+    }
+
+    fn bounds(&self) -> bounds::BoundingBox {
+        let radius = self.minimum.abs().max(self.maximum.abs());
+        let obj_bounds = bounds::new(primatives::point(-radius, self.minimum, -radius), primatives::point(radius, self.maximum, radius));
+        return obj_bounds.transformed(self.transformation);
+    }
+}
+
+impl shapes::Surface for Cone {
+    fn normal_at(&self, p: primatives::PointT) -> primatives::Vec3T {
+        p.check_type(primatives::TYPE_PNT);
+
+        let point = self.transformation.inverse() * p;
+        let dist = (point.x * point.x) + (point.z * point.z);
+
+        let obj_normal =
+            if dist < point.y * point.y && point.y >= self.maximum - EPSILON {
+                primatives::vec3(0.0, 1.0, 0.0)
+            } else if dist < point.y * point.y && point.y <= self.minimum + EPSILON {
+                primatives::vec3(0.0, -1.0, 0.0)
+            } else if dist == 0.0 {
+                //The very tip has no slope, so just pick one
+                primatives::vec3(0.0, 1.0, 0.0)
+            } else {
+                //The wall slopes at 45 degrees, away from the tip
+                let y = if point.y > 0.0 { -dist.sqrt() } else { dist.sqrt() };
+                primatives::vec3(point.x, y, point.z)
+            };
+
+        let mut world_normal = self.transformation.inverse().transposed() * obj_normal;
+        world_normal.w = 0.0;
+
+        return world_normal.normalized()
+    }
+
+    fn get_material(&self) -> material::Material {
+        return self.material.clone();
+    }
+}
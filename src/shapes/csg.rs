@@ -0,0 +1,142 @@
+use std::ops;
+
+use crate::primatives;
+use crate::ray;
+use crate::matrix;
+use crate::shapes;
+use crate::shapes::{Shape};
+use crate::shapes::intersection;
+use crate::shapes::bounds;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Operation {
+    //Everything in either shape
+    Union,
+
+    //Only what is in both shapes
+    Intersection,
+
+    //What is in the left shape, with the right shape cut out of it
+    Difference
+}
+
+/* Constructive solid geometry: a shape made by combining two other
+ * shapes. The surface is made of whichever parts of the two shapes'
+ * surfaces the operation keeps. Either side can be another Csg, or a
+ * group, to build up more complicated solids. Like a group, a Csg has
+ * no surface of its own: intersections keep the shape that was hit
+ */
+#[derive(Clone, Copy)]
+pub struct Csg<'a> {
+    pub operation: Operation,
+    pub left: &'a (dyn Shape + 'a),
+    pub right: &'a (dyn Shape + 'a),
+    pub transformation: matrix::Matrix4x4
+}
+
+pub fn new<'a>(op: Operation, left: &'a dyn Shape, right: &'a dyn Shape) -> Csg<'a> {
+    return Csg {
+        operation: op,
+        left: left,
+        right: right,
+        transformation: matrix::IDENTITY_MATRIX_4X4
+    }
+}
+
+impl<'a> ops::Mul<Csg<'a>> for matrix::Matrix4x4 {
+    type Output = Csg<'a>;
+    fn mul(self, c: Csg<'a>) -> Csg<'a> {
+        return Csg {
+            transformation: c.transformation * self,
+            ..c
+        }
+    }
+}
+
+/* Decides whether an intersection is on the surface of the combined
+ * shape. left_hit is true if the intersection is on the left shape,
+ * in_left and in_right say whether the ray is currently inside of
+ * each shape
+ */
+pub fn intersection_allowed(op: Operation, left_hit: bool, in_left: bool, in_right: bool) -> bool {
+    return match op {
+        Operation::Union => (left_hit && !in_right) || (!left_hit && !in_left),
+        Operation::Intersection => (left_hit && in_right) || (!left_hit && in_left),
+        Operation::Difference => (left_hit && !in_right) || (!left_hit && in_left)
+    }
+}
+
+impl<'a> Csg<'a> {
+    /* Walks through the intersections with the left and right shapes in
+     * order, keeping track of whether the ray is inside of each shape,
+     * and keeps the ones the operation allows. Intersections are kept
+     * apart by the side they came from, not by the shape that was hit,
+     * so a shape used on both sides still counts once for each side.
+     * Shapes return intersections behind the ray origin too, so the ray
+     * always starts out outside of both shapes
+     */
+    pub fn filter_intersections<'b>(&self, left: Vec<intersection::Intersection<'b>>,
+    right: Vec<intersection::Intersection<'b>>) -> Vec<intersection::Intersection<'b>> {
+        let mut sides = vec![];
+        for i in left {
+            sides.push((true, i));
+        }
+        for i in right {
+            sides.push((false, i));
+        }
+        sides.sort_by(|a, b| a.1.time.total_cmp(&b.1.time));
+
+        let mut in_left = false;
+        let mut in_right = false;
+
+        let mut ret_val = vec![];
+        for (left_hit, i) in sides {
+            if intersection_allowed(self.operation, left_hit, in_left, in_right) {
+                ret_val.push(i);
+            }
+
+            if left_hit {
+                in_left = !in_left;
+            } else {
+                in_right = !in_right;
+            }
+        }
+
+        return ret_val
+    }
+}
+
+impl shapes::Shape for Csg<'_> {
+    fn intersect(&self, r_input: ray::Ray) -> Vec<intersection::Intersection<'_>> {
+        let r = self.transformation.inverse() * r_input;
+
+        let mut ret_val = vec![];
+        for i in self.filter_intersections(self.left.intersect(r), self.right.intersect(r)) {
+            ret_val.push(i.to_parent_space(self.transformation, r_input));
+        }
+
+        return ret_val
+    }
+
+    fn world_to_object(&self, p: primatives::PointT) -> primatives::PointT {
+        return self.transformation.inverse() * p;
+    }
+
+    fn transformation(&self) -> matrix::Matrix4x4 {
+        return self.transformation;
+    }
+
+    //Whatever is left of a difference fits inside of the left shape
+    fn bounds(&self) -> bounds::BoundingBox {
+        let local_bounds = match self.operation {
+            Operation::Difference => self.left.bounds(),
+            _ => self.left.bounds().merge(self.right.bounds())
+        };
+
+        return local_bounds.transformed(self.transformation);
+    }
+
+    fn includes(&self, s: &dyn Shape) -> bool {
+        return self.left.includes(s) || self.right.includes(s);
+    }
+}
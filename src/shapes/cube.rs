@@ -0,0 +1,117 @@
+use std::ops;
+
+use crate::primatives;
+use crate::ray;
+use crate::matrix;
+use crate::material;
+use crate::shapes;
+use crate::shapes::intersection;
+use crate::shapes::bounds;
+
+/* An axis aligned box. Before transformations are applied, it spans
+ * from -1 to 1 on every axis. Scale and move it to get other boxes
+ */
+#[derive(Debug, Clone)]
+pub struct Cube {
+    pub transformation: matrix::Matrix4x4,
+    pub material: material::Material
+}
+
+pub fn new() -> Cube {
+    return Cube {
+        transformation: matrix::IDENTITY_MATRIX_4X4,
+        material: material::new_default()
+    }
+}
+
+impl ops::Mul<Cube> for matrix::Matrix4x4 {
+    type Output = Cube;
+    fn mul(self, c: Cube) -> Cube {
+        return Cube {
+            transformation: c.transformation * self,
+            material: c.material
+        }
+    }
+}
+
+/* Finds the times the ray crosses the two faces of the cube
+ * at -1 and 1 on one axis. Returned smallest first
+ */
+fn check_axis(origin: f64, direction: f64) -> (f64, f64) {
+    let tmin_numerator = -1.0 - origin;
+    let tmax_numerator = 1.0 - origin;
+
+    //Dividing by zero gives infinity, which is what a parallel ray should get
+    let (tmin, tmax) = (tmin_numerator / direction, tmax_numerator / direction);
+
+    return if tmin > tmax { (tmax, tmin) } else { (tmin, tmax) }
+}
+
+impl shapes::Shape for Cube {
+
+    /* The slab method: the cube is the space where the three pairs of
+     * faces (slabs) overlap. The ray enters the cube when it has entered
+     * the last of the three slabs, and leaves when it leaves the first one.
+     * If it leaves one before it has entered another, it misses
+     */
+    fn intersect(&self, r_input: ray::Ray) -> Vec<intersection::Intersection<'_>> {
+        let r = self.transformation.inverse() * r_input;
+
+        let (xtmin, xtmax) = check_axis(r.origin.x, r.direction.x);
+        let (ytmin, ytmax) = check_axis(r.origin.y, r.direction.y);
+        let (ztmin, ztmax) = check_axis(r.origin.z, r.direction.z);
+
+        let tmin = xtmin.max(ytmin).max(ztmin);
+        let tmax = xtmax.min(ytmax).min(ztmax);
+
+        if tmin > tmax {
+            return vec![]
+        }
+
+        return vec![
+            intersection::new(tmin, r_input.position(tmin), self, r_input),
+            intersection::new(tmax, r_input.position(tmax), self, r_input)
+        ]
+    }
+
+    fn world_to_object(&self, p: primatives::PointT) -> primatives::PointT {
+        return self.transformation.inverse() * p;
+    }
+
+    fn transformation(&self) -> matrix::Matrix4x4 {
+        return self.transformation;
+    }
+
+    fn bounds(&self) -> bounds::BoundingBox {
+        let obj_bounds = bounds::new(primatives::point(-1.0, -1.0, -1.0), primatives::point(1.0, 1.0, 1.0));
+        return obj_bounds.transformed(self.transformation);
+    }
+}
+
+impl shapes::Surface for Cube {
+    fn normal_at(&self, p: primatives::PointT) -> primatives::Vec3T {
+        p.check_type(primatives::TYPE_PNT);
+
+        let point = self.transformation.inverse() * p;
+
+        //The face the point is on is the one along its largest component
+        let (ax, ay, az) = (point.x.abs(), point.y.abs(), point.z.abs());
+        let obj_normal =
+            if ax >= ay && ax >= az {
+                primatives::vec3(point.x, 0.0, 0.0)
+            } else if ay >= az {
+                primatives::vec3(0.0, point.y, 0.0)
+            } else {
+                primatives::vec3(0.0, 0.0, point.z)
+            };
+
+        let mut world_normal = self.transformation.inverse().transposed() * obj_normal;
+        world_normal.w = 0.0;
+
+        return world_normal.normalized()
+    }
+
+    fn get_material(&self) -> material::Material {
+        return self.material.clone();
+    }
+}
@@ -0,0 +1,157 @@
+use std::ops;
+
+use crate::primatives;
+use crate::ray;
+use crate::matrix;
+use crate::material;
+use crate::shapes;
+use crate::shapes::intersection;
+use crate::shapes::bounds;
+
+//Margin of error for rays parallel to the walls, and for points on the caps
+const EPSILON: f64 = 0.0001;
+
+/* A cylinder of radius 1, centered on the y axis. It goes on forever
+ * unless it is cut off at `minimum` and/or `maximum` along y. A cut off
+ * cylinder is hollow, unless `closed` is set, which puts caps on the ends
+ */
+#[derive(Debug, Clone)]
+pub struct Cylinder {
+    pub transformation: matrix::Matrix4x4,
+    pub material: material::Material,
+    pub minimum: f64,
+    pub maximum: f64,
+    pub closed: bool
+}
+
+pub fn new() -> Cylinder {
+    return Cylinder {
+        transformation: matrix::IDENTITY_MATRIX_4X4,
+        material: material::new_default(),
+        minimum: f64::NEG_INFINITY,
+        maximum: f64::INFINITY,
+        closed: false
+    }
+}
+
+impl ops::Mul<Cylinder> for matrix::Matrix4x4 {
+    type Output = Cylinder;
+    fn mul(self, c: Cylinder) -> Cylinder {
+        return Cylinder {
+            transformation: c.transformation * self,
+            ..c
+        }
+    }
+}
+
+impl Cylinder {
+    //Is the point at time t on the ray within radius of the y axis?
+    fn within_cap(r: ray::Ray, t: f64, radius: f64) -> bool {
+        let x = r.origin.x + t * r.direction.x;
+        let z = r.origin.z + t * r.direction.z;
+        return (x * x) + (z * z) <= radius * radius
+    }
+
+    //Times at which the ray goes through the end caps
+    fn intersect_caps(&self, r: ray::Ray) -> Vec<f64> {
+        let mut ret_val = vec![];
+
+        //A ray parallel to the caps cannot go through them
+        if !self.closed || r.direction.y.abs() < EPSILON {
+            return ret_val
+        }
+
+        for cap in [self.minimum, self.maximum] {
+            let t = (cap - r.origin.y) / r.direction.y;
+            if Cylinder::within_cap(r, t, 1.0) {
+                ret_val.push(t);
+            }
+        }
+
+        return ret_val
+    }
+}
+
+impl shapes::Shape for Cylinder {
+
+    /* For the walls, this is the same as the sphere's quadratic, except
+     * that y is left out: x * x + z * z = 1 for every point on the wall.
+     * Wall hits outside of minimum and maximum are thrown away, and then
+     * the caps are checked separately
+     */
+    fn intersect(&self, r_input: ray::Ray) -> Vec<intersection::Intersection<'_>> {
+        let r = self.transformation.inverse() * r_input;
+        let mut t_values = vec![];
+
+        let a = (r.direction.x * r.direction.x) + (r.direction.z * r.direction.z);
+
+        //If a is zero, the ray is parallel to the y axis, and can only hit the caps
+        if a.abs() >= EPSILON {
+            let b = 2.0 * ((r.origin.x * r.direction.x) + (r.origin.z * r.direction.z));
+            let c = (r.origin.x * r.origin.x) + (r.origin.z * r.origin.z) - 1.0;
+            let d = (b * b) - 4.0 * a * c;
+
+            //Missing the infinite cylinder means missing the caps as well
+            if d < 0.0 {
+                return vec![]
+            }
+
+            for t in [(-b - d.sqrt()) / (2.0 * a), (-b + d.sqrt()) / (2.0 * a)] {
+                let y = r.origin.y + t * r.direction.y;
+                if self.minimum < y && y < self.maximum {
+                    t_values.push(t);
+                }
+            }
+        }
+
+        t_values.extend(self.intersect_caps(r));
+
+        let mut ret_val = vec![];
+        for t in t_values {
+            ret_val.push(intersection::new(t, r_input.position(t), self, r_input));
+        }
+
+        return ret_val
+    }
+
+    fn world_to_object(&self, p: primatives::PointT) -> primatives::PointT {
+        return self.transformation.inverse() * p;
+    }
+
+    fn transformation(&self) -> matrix::Matrix4x4 {
+        return self.transformation;
+    }
+
+    fn bounds(&self) -> bounds::BoundingBox {
+        let obj_bounds = bounds::new(primatives::point(-1.0, self.minimum, -1.0), primatives::point(1.0, self.maximum, 1.0));
+        return obj_bounds.transformed(self.transformation);
+    }
+}
+
+impl shapes::Surface for Cylinder {
+    fn normal_at(&self, p: primatives::PointT) -> primatives::Vec3T {
+        p.check_type(primatives::TYPE_PNT);
+
+        let point = self.transformation.inverse() * p;
+        let dist = (point.x * point.x) + (point.z * point.z);
+
+        //Points on the caps face straight up or down, the walls face out from the y axis
+        let obj_normal =
+            if dist < 1.0 && point.y >= self.maximum - EPSILON {
+                primatives::vec3(0.0, 1.0, 0.0)
+            } else if dist < 1.0 && point.y <= self.minimum + EPSILON {
+                primatives::vec3(0.0, -1.0, 0.0)
+            } else {
+                primatives::vec3(point.x, 0.0, point.z)
+            };
+
+        let mut world_normal = self.transformation.inverse().transposed() * obj_normal;
+        world_normal.w = 0.0;
+
+        return world_normal.normalized()
+    }
+
+    fn get_material(&self) -> material::Material {
+        return self.material.clone();
+    }
+}
@@ -0,0 +1,99 @@
+use std::ops;
+
+use crate::primatives;
+use crate::ray;
+use crate::matrix;
+use crate::shapes;
+use crate::shapes::{Shape};
+use crate::shapes::intersection;
+use crate::shapes::bounds;
+
+/* A collection of shapes that are moved around together. The group's
+ * transformation is applied on top of each child's own transformation,
+ * and groups can hold other groups, so whole models can be built up
+ * from parts. The same children can be put in several groups with
+ * different transformations to place copies of a model around a scene.
+ *
+ * A group has no surface of its own. Intersections keep the child that
+ * was hit as their shape, so each child is shaded with its own material,
+ * and Intersection::normal_at goes through every group the child is in.
+ */
+#[derive(Clone)]
+pub struct Group<'a> {
+    pub transformation: matrix::Matrix4x4,
+    children: Vec<&'a (dyn Shape + 'a)>,
+
+    //Box around all of the children, in the group's own space
+    local_bounds: bounds::BoundingBox
+}
+
+pub fn new<'a>() -> Group<'a> {
+    return Group {
+        transformation: matrix::IDENTITY_MATRIX_4X4,
+        children: vec![],
+        local_bounds: bounds::new_empty()
+    }
+}
+
+impl<'a> ops::Mul<Group<'a>> for matrix::Matrix4x4 {
+    type Output = Group<'a>;
+    fn mul(self, g: Group<'a>) -> Group<'a> {
+        return Group {
+            transformation: g.transformation * self,
+            ..g
+        }
+    }
+}
+
+impl<'a> Group<'a> {
+    pub fn add_child(&mut self, s: &'a (dyn Shape + 'a)) {
+        self.local_bounds = self.local_bounds.merge(s.bounds());
+        self.children.push(s);
+    }
+
+    pub fn children(&self) -> &[&'a (dyn Shape + 'a)] {
+        return &self.children;
+    }
+}
+
+impl shapes::Shape for Group<'_> {
+
+    /* The ray is moved into the group's space, and tested against each
+     * child there. Each child then works out the ray in its own space,
+     * and so on down the hierarchy. On the way back up, each group moves
+     * the intersections back out into its parent's space
+     */
+    fn intersect(&self, r_input: ray::Ray) -> Vec<intersection::Intersection<'_>> {
+        let r = self.transformation.inverse() * r_input;
+
+        //Skip every child at once if the ray misses all of them
+        if !self.local_bounds.intersects(r) {
+            return vec![]
+        }
+
+        let mut ret_val = vec![];
+        for child in &self.children {
+            for i in child.intersect(r) {
+                ret_val.push(i.to_parent_space(self.transformation, r_input));
+            }
+        }
+
+        return ret_val
+    }
+
+    fn world_to_object(&self, p: primatives::PointT) -> primatives::PointT {
+        return self.transformation.inverse() * p;
+    }
+
+    fn transformation(&self) -> matrix::Matrix4x4 {
+        return self.transformation;
+    }
+
+    fn bounds(&self) -> bounds::BoundingBox {
+        return self.local_bounds.transformed(self.transformation);
+    }
+
+    fn includes(&self, s: &dyn Shape) -> bool {
+        return self.children.iter().any(|c| c.includes(s));
+    }
+}
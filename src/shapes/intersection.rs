@@ -1,20 +1,65 @@
 use crate::primatives;
 use crate::ray;
-use crate::shapes::{Shape};
+use crate::matrix;
+use crate::shapes::{Surface};
+
+//Distance a point is pushed off of a surface to avoid self-intersection
+pub const OVER_POINT_EPSILON: f64 = 0.0001;
 
 #[derive(Copy, Clone)]
 pub struct Intersection<'a> {
     pub time: f64,
     pub location: primatives::PointT,
-    pub shape: &'a (dyn Shape + 'a),
+    pub shape: &'a (dyn Surface + 'a), //The shape that was hit, never a group or CSG
     pub inside: bool, //True if the ray origin is inside the sphere
     pub ray: ray::Ray,
     pub eyev: primatives::Vec3T,
-    pub normalv: primatives::Vec3T
+    pub normalv: primatives::Vec3T,
+    pub reflectv: primatives::Vec3T, //Direction the ray bounces off of the surface
+
+    /* The location, nudged slightly along the normal. Rays cast from
+     * the surface (eg, shadow rays) start here, so floating point error
+     * does not cause them to hit the surface they started on ("acne")
+     */
+    pub over_point: primatives::PointT,
+
+    //Same as over_point, but below the surface. Refracted rays start here
+    pub under_point: primatives::PointT,
+
+    /* over_point, in the shape's own space. Patterns are painted on in
+     * object space, so they move along with the shape. This is found when
+     * the intersection is made, so it is still right for shapes in groups
+     */
+    pub object_point: primatives::PointT,
+
+    /* Refractive indices of the material the ray is leaving (n1) and
+     * entering (n2). These depend on every other object the ray has
+     * passed through, so they are 1.0 (vacuum) until filled in by
+     * refractive_indices()
+     */
+    pub n1: f64,
+    pub n2: f64,
+
+    /* Where on the surface the ray hit, for shapes that keep track of it.
+     * On triangles, these are the barycentric weights of the second and
+     * third corners. Other shapes leave them at 0.0
+     */
+    pub u: f64,
+    pub v: f64,
+
+    /* Every group (or CSG) transformation the shape sits inside of,
+     * combined. Moves things from the space the shape sits in out to
+     * the space the ray came from. Identity for shapes not in a group
+     */
+    pub parent_transformation: matrix::Matrix4x4
+}
+
+pub fn new(t: f64, l: primatives::PointT, s: &dyn Surface, r: ray::Ray) -> Intersection<'_> {
+    return new_with_uv(t, l, s, r, 0.0, 0.0);
 }
 
-pub fn new<'a>(t: f64, l: primatives::PointT, s: &'a dyn Shape, r: ray::Ray) -> Intersection {
-    let mut nv = s.normal_at(l);
+pub fn new_with_uv(t: f64, l: primatives::PointT, s: &dyn Surface, r: ray::Ray, u: f64, v: f64) -> Intersection<'_> {
+    let mut nv = s.normal_at_uv(l, u, v);
     let ev = -r.direction;
 
     let is_inside =
@@ -25,6 +70,33 @@ pub fn new<'a>(t: f64, l: primatives::PointT, s: &'a dyn Shape, r: ray::Ray) ->
             false
         };
 
+    /* Offsets use the real normal of the surface, bumps or not, so that
+     * they end up on the correct side of it
+     */
+    let op = l + nv * OVER_POINT_EPSILON;
+    let up = l - nv * OVER_POINT_EPSILON;
+    let object_point = s.world_to_object(op);
+
+    /* A bumpy material tilts the normal. The bumps are worked out in the
+     * shape's own space, so the normal is moved in there (by the transpose
+     * of the transformation, the inverse of how normals are moved out),
+     * tilted, and moved back out. If that tilts it away from the eye, it
+     * is flipped back, just like above
+     */
+    if let Some(b) = s.get_material().bump {
+        let transformation = s.transformation();
+
+        let mut object_normal = transformation.transposed() * nv;
+        object_normal.w = 0.0;
+        let mut bumped = transformation.inverse().transposed() * b.perturb(object_point, object_normal.normalized());
+        bumped.w = 0.0;
+
+        nv = bumped.normalized();
+        if primatives::dot_product(nv, ev) < 0.0 {
+            nv = -nv;
+        }
+    }
+
     return Intersection {
         time: t,
         location: l,
@@ -33,5 +105,115 @@ pub fn new<'a>(t: f64, l: primatives::PointT, s: &'a dyn Shape, r: ray::Ray) ->
         ray: r,
         eyev: ev,
         normalv: nv,
+        reflectv: nv.reflect(r.direction),
+        over_point: op,
+        under_point: up,
+        object_point: object_point,
+        n1: 1.0,
+        n2: 1.0,
+        u: u,
+        v: v,
+        parent_transformation: matrix::IDENTITY_MATRIX_4X4
     }
+}
+
+impl<'a> Intersection<'a> {
+    /* Shapes inside of a group are intersected in the group's own space.
+     * This moves an intersection found that way out into the space the
+     * group sits in, where the ray r came from. The time along the ray
+     * does not change, only the location and the vectors do
+     */
+    pub fn to_parent_space(&self, transformation: matrix::Matrix4x4, r: ray::Ray) -> Intersection<'a> {
+        let mut nv = transformation.inverse().transposed() * self.normalv;
+        nv.w = 0.0;
+        nv = nv.normalized();
+
+        let l = r.position(self.time);
+
+        return Intersection {
+            location: l,
+            ray: r,
+            eyev: -r.direction,
+            normalv: nv,
+            reflectv: nv.reflect(r.direction),
+            over_point: l + nv * OVER_POINT_EPSILON,
+            under_point: l - nv * OVER_POINT_EPSILON,
+            parent_transformation: transformation * self.parent_transformation,
+            ..*self
+        }
+    }
+
+    /* The normal of the shape that was hit, at p, with p in the same space
+     * as the ray. p is moved down through every group the shape sits in,
+     * and the normal is moved back up through them
+     */
+    pub fn normal_at(&self, p: primatives::PointT) -> primatives::Vec3T {
+        p.check_type(primatives::TYPE_PNT);
+
+        let local_point = self.parent_transformation.inverse() * p;
+        let local_normal = self.shape.normal_at_uv(local_point, self.u, self.v);
+
+        let mut nv = self.parent_transformation.inverse().transposed() * local_normal;
+        nv.w = 0.0;
+
+        return nv.normalized()
+    }
+
+    //Moves p (in the same space as the ray) into the hit shape's own space
+    pub fn world_to_object(&self, p: primatives::PointT) -> primatives::PointT {
+        return self.shape.world_to_object(self.parent_transformation.inverse() * p);
+    }
+}
+
+//Returns the visible intersection, ie, the nearest one in front of the ray origin
+pub fn hit<'a>(intersections: &[Intersection<'a>]) -> Option<Intersection<'a>> {
+    let mut ret_val: Option<Intersection<'a>> = None;
+
+    for i in intersections {
+        if i.time >= 0.0 && (ret_val.is_none() || i.time < ret_val.unwrap().time) {
+            ret_val = Some(*i);
+        }
+    }
+
+    return ret_val
+}
+
+fn same_shape(s1: &dyn Surface, s2: &dyn Surface) -> bool {
+    return std::ptr::addr_eq(s1 as *const dyn Surface, s2 as *const dyn Surface)
+}
+
+/* Walks the sorted intersections up to the hit, keeping a list of the
+ * objects the ray is currently inside of. The last object entered before
+ * the hit gives n1, and the last object still containing the ray after
+ * the hit gives n2. Returns the hit with n1 and n2 filled in
+ */
+pub fn refractive_indices<'a>(hit: Intersection<'a>, intersections: &[Intersection<'a>]) -> Intersection<'a> {
+    let mut containers: Vec<&'a dyn Surface> = vec![];
+    let mut ret_val = hit;
+
+    for i in intersections {
+        let is_hit = i.time == hit.time && same_shape(i.shape, hit.shape);
+
+        if is_hit {
+            ret_val.n1 = match containers.last() {
+                Some(s) => s.get_material().refractive_index,
+                None => 1.0
+            };
+        }
+
+        match containers.iter().position(|s| same_shape(*s, i.shape)) {
+            Some(index) => { containers.remove(index); },
+            None => containers.push(i.shape)
+        }
+
+        if is_hit {
+            ret_val.n2 = match containers.last() {
+                Some(s) => s.get_material().refractive_index,
+                None => 1.0
+            };
+            break;
+        }
+    }
+
+    return ret_val
 }
\ No newline at end of file
@@ -1,12 +1,51 @@
 pub mod intersection;
 pub mod sphere;
+pub mod plane;
+pub mod cube;
+pub mod cylinder;
+pub mod cone;
+pub mod triangle;
+pub mod group;
+pub mod csg;
+pub mod bounds;
 
 use crate::ray;
 use crate::primatives;
 use crate::material;
+use crate::matrix;
 
-pub trait Shape {
-    fn intersect(&self, r_input: ray::Ray) -> Vec<intersection::Intersection> ;
+//Shapes are shared between render threads, so they must be Sync
+pub trait Shape: Sync {
+    fn intersect(&self, r_input: ray::Ray) -> Vec<intersection::Intersection<'_>>;
+
+    //Moves a point from the space the shape sits in into the shape's own space
+    fn world_to_object(&self, p: primatives::PointT) -> primatives::PointT;
+
+    //Moves things from the shape's own space into the space the shape sits in
+    fn transformation(&self) -> matrix::Matrix4x4;
+
+    //A world space box that the whole shape fits inside of
+    fn bounds(&self) -> bounds::BoundingBox;
+
+    /* Is s this shape, or (for shapes made of other shapes) one of
+     * the shapes it is made of? Shapes are compared by address
+     */
+    fn includes(&self, s: &dyn Shape) -> bool {
+        return std::ptr::addr_eq(self as *const Self, s as *const dyn Shape);
+    }
+}
+
+/* A shape with a surface of its own, that intersections land on. Shapes
+ * made of other shapes (groups and CSG) are not surfaces. Their
+ * intersections keep the part that was hit, so the normal and material
+ * always come from that part (see Intersection::normal_at)
+ */
+pub trait Surface: Shape {
     fn normal_at(&self, p: primatives::PointT) -> primatives::Vec3T;
+
+    //Shapes whose normal depends on where the ray hit (u, v) override this
+    fn normal_at_uv(&self, p: primatives::PointT, _u: f64, _v: f64) -> primatives::Vec3T {
+        return self.normal_at(p);
+    }
     fn get_material(&self) -> material::Material;
-}
\ No newline at end of file
+}
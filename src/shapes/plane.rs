@@ -0,0 +1,87 @@
+use std::ops;
+
+use crate::primatives;
+use crate::ray;
+use crate::matrix;
+use crate::material;
+use crate::shapes;
+use crate::shapes::intersection;
+use crate::shapes::bounds;
+
+//Rays this close to parallel with the plane are treated as missing it
+pub const PARALLEL_EPSILON: f64 = 0.0001;
+
+/* An infinite plane. Before transformations are applied, the plane
+ * lies flat in x and z, passing through the origin
+ */
+#[derive(Debug, Clone)]
+pub struct Plane {
+    pub transformation: matrix::Matrix4x4,
+    pub material: material::Material
+}
+
+pub fn new() -> Plane {
+    return Plane {
+        transformation: matrix::IDENTITY_MATRIX_4X4,
+        material: material::new_default()
+    }
+}
+
+impl ops::Mul<Plane> for matrix::Matrix4x4 {
+    type Output = Plane;
+    fn mul(self, p: Plane) -> Plane {
+        return Plane {
+            transformation: p.transformation * self,
+            material: p.material
+        }
+    }
+}
+
+impl shapes::Shape for Plane {
+
+    /* The untransformed plane is every point where y = 0, so the ray
+     * hits it when origin.y + time * direction.y = 0. A ray with no
+     * y component either misses the plane or lies in it, and both are
+     * treated as a miss.
+     */
+    fn intersect(&self, r_input: ray::Ray) -> Vec<intersection::Intersection<'_>> {
+        let r = self.transformation.inverse() * r_input;
+
+        if r.direction.y.abs() < PARALLEL_EPSILON {
+            return vec![]
+        }
+
+        let t = -r.origin.y / r.direction.y;
+        return vec![intersection::new(t, r_input.position(t), self, r_input)]
+    }
+
+    fn world_to_object(&self, p: primatives::PointT) -> primatives::PointT {
+        return self.transformation.inverse() * p;
+    }
+
+    fn transformation(&self) -> matrix::Matrix4x4 {
+        return self.transformation;
+    }
+
+    fn bounds(&self) -> bounds::BoundingBox {
+        return bounds::new_infinite();
+    }
+}
+
+impl shapes::Surface for Plane {
+    fn normal_at(&self, p: primatives::PointT) -> primatives::Vec3T {
+        p.check_type(primatives::TYPE_PNT);
+
+        //The plane is flat, so the normal is the same at every point
+        let obj_normal = primatives::vec3(0.0, 1.0, 0.0);
+
+        let mut world_normal = self.transformation.inverse().transposed() * obj_normal;
+        world_normal.w = 0.0;
+
+        return world_normal.normalized()
+    }
+
+    fn get_material(&self) -> material::Material {
+        return self.material.clone();
+    }
+}
@@ -6,8 +6,9 @@ use crate::matrix;
 use crate::material;
 use crate::shapes;
 use crate::shapes::intersection;
+use crate::shapes::bounds;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub struct Sphere {
     pub radius: f64,
     pub origin: primatives::Tuple,
@@ -47,7 +48,7 @@ impl shapes::Shape for Sphere {
      * this function solves that quadratic to find the points on the ray
      * that intersect the sphere.
      */
-    fn intersect(&self, r_input: ray::Ray) -> Vec<intersection::Intersection> {
+    fn intersect(&self, r_input: ray::Ray) -> Vec<intersection::Intersection<'_>> {
         let r = self.transformation.inverse() * r_input;
 
         let sphere_to_ray = r.origin - self.origin;
@@ -76,6 +77,22 @@ impl shapes::Shape for Sphere {
         return ret_val
     }
 
+    fn world_to_object(&self, p: primatives::PointT) -> primatives::PointT {
+        return self.transformation.inverse() * p;
+    }
+
+    fn transformation(&self) -> matrix::Matrix4x4 {
+        return self.transformation;
+    }
+
+    fn bounds(&self) -> bounds::BoundingBox {
+        let r = primatives::vec3(self.radius, self.radius, self.radius);
+        let obj_bounds = bounds::new(self.origin - r, self.origin + r);
+        return obj_bounds.transformed(self.transformation);
+    }
+}
+
+impl shapes::Surface for Sphere {
     fn normal_at(&self, p: primatives::PointT) -> primatives::Vec3T {
         p.check_type(primatives::TYPE_PNT);
 
@@ -94,6 +111,6 @@ impl shapes::Shape for Sphere {
     }
 
     fn get_material(&self) -> material::Material {
-        return self.material;
+        return self.material.clone();
     }
 }
\ No newline at end of file
@@ -0,0 +1,270 @@
+use std::ops;
+
+use crate::primatives;
+use crate::ray;
+use crate::matrix;
+use crate::material;
+use crate::shapes;
+use crate::shapes::intersection;
+use crate::shapes::bounds;
+
+//Rays this close to parallel with a triangle are treated as missing it
+pub const PARALLEL_EPSILON: f64 = 0.0001;
+
+//A flat triangle. The edges and normal never change, so they are worked out up front
+#[derive(Debug, Clone)]
+pub struct Triangle {
+    pub p1: primatives::PointT,
+    pub p2: primatives::PointT,
+    pub p3: primatives::PointT,
+    pub e1: primatives::Vec3T, //p1 to p2
+    pub e2: primatives::Vec3T, //p1 to p3
+    pub normal: primatives::Vec3T,
+    pub texture_coords: Option<[(f64, f64); 3]>, //Texture u and v at p1, p2 and p3, if there are any
+    pub transformation: matrix::Matrix4x4,
+    pub material: material::Material
+}
+
+/* A triangle with a normal at each corner. The normal across the face
+ * blends between them, so a mesh of these looks smoothly curved, even
+ * though the triangles themselves are flat
+ */
+#[derive(Debug, Clone)]
+pub struct SmoothTriangle {
+    pub p1: primatives::PointT,
+    pub p2: primatives::PointT,
+    pub p3: primatives::PointT,
+    pub n1: primatives::Vec3T,
+    pub n2: primatives::Vec3T,
+    pub n3: primatives::Vec3T,
+    pub e1: primatives::Vec3T,
+    pub e2: primatives::Vec3T,
+    pub texture_coords: Option<[(f64, f64); 3]>,
+    pub transformation: matrix::Matrix4x4,
+    pub material: material::Material
+}
+
+pub fn new(p1: primatives::PointT, p2: primatives::PointT, p3: primatives::PointT) -> Triangle {
+    p1.check_type(primatives::TYPE_PNT);
+    p2.check_type(primatives::TYPE_PNT);
+    p3.check_type(primatives::TYPE_PNT);
+
+    let e1 = p2 - p1;
+    let e2 = p3 - p1;
+
+    return Triangle {
+        p1: p1,
+        p2: p2,
+        p3: p3,
+        e1: e1,
+        e2: e2,
+        normal: primatives::cross_product(e2, e1).normalized(),
+        texture_coords: None,
+        transformation: matrix::IDENTITY_MATRIX_4X4,
+        material: material::new_default()
+    }
+}
+
+pub fn new_smooth(p1: primatives::PointT, p2: primatives::PointT, p3: primatives::PointT,
+n1: primatives::Vec3T, n2: primatives::Vec3T, n3: primatives::Vec3T) -> SmoothTriangle {
+    p1.check_type(primatives::TYPE_PNT);
+    p2.check_type(primatives::TYPE_PNT);
+    p3.check_type(primatives::TYPE_PNT);
+    n1.check_type(primatives::TYPE_VEC);
+    n2.check_type(primatives::TYPE_VEC);
+    n3.check_type(primatives::TYPE_VEC);
+
+    return SmoothTriangle {
+        p1: p1,
+        p2: p2,
+        p3: p3,
+        n1: n1,
+        n2: n2,
+        n3: n3,
+        e1: p2 - p1,
+        e2: p3 - p1,
+        texture_coords: None,
+        transformation: matrix::IDENTITY_MATRIX_4X4,
+        material: material::new_default()
+    }
+}
+
+impl ops::Mul<Triangle> for matrix::Matrix4x4 {
+    type Output = Triangle;
+    fn mul(self, t: Triangle) -> Triangle {
+        return Triangle {
+            transformation: t.transformation * self,
+            ..t
+        }
+    }
+}
+
+impl ops::Mul<SmoothTriangle> for matrix::Matrix4x4 {
+    type Output = SmoothTriangle;
+    fn mul(self, t: SmoothTriangle) -> SmoothTriangle {
+        return SmoothTriangle {
+            transformation: t.transformation * self,
+            ..t
+        }
+    }
+}
+
+/* Möller–Trumbore intersection. Any point on the triangle can be written
+ * as p1 + u * e1 + v * e2, with u and v both positive and adding up to at
+ * most 1. Setting that equal to the ray and solving with Cramer's rule
+ * gives t, u and v all at once. Returns None if the ray misses
+ */
+fn moller_trumbore(p1: primatives::PointT, e1: primatives::Vec3T, e2: primatives::Vec3T,
+r: ray::Ray) -> Option<(f64, f64, f64)> {
+    let dir_cross_e2 = primatives::cross_product(r.direction, e2);
+    let det = primatives::dot_product(e1, dir_cross_e2);
+    if det.abs() < PARALLEL_EPSILON {
+        return None
+    }
+
+    let f = 1.0 / det;
+    let p1_to_origin = r.origin - p1;
+    let u = f * primatives::dot_product(p1_to_origin, dir_cross_e2);
+    if !(0.0..=1.0).contains(&u) {
+        return None
+    }
+
+    let origin_cross_e1 = primatives::cross_product(p1_to_origin, e1);
+    let v = f * primatives::dot_product(r.direction, origin_cross_e1);
+    if v < 0.0 || u + v > 1.0 {
+        return None
+    }
+
+    let t = f * primatives::dot_product(e2, origin_cross_e1);
+    return Some((t, u, v))
+}
+
+/* Works out u and v (as in moller_trumbore()) for a point already known
+ * to be on the triangle
+ */
+fn barycentric(p1: primatives::PointT, e1: primatives::Vec3T, e2: primatives::Vec3T,
+p: primatives::PointT) -> (f64, f64) {
+    let to_p = p - p1;
+    let d11 = primatives::dot_product(e1, e1);
+    let d12 = primatives::dot_product(e1, e2);
+    let d22 = primatives::dot_product(e2, e2);
+    let dp1 = primatives::dot_product(to_p, e1);
+    let dp2 = primatives::dot_product(to_p, e2);
+    let denominator = d11 * d22 - d12 * d12;
+
+    return ((d22 * dp1 - d12 * dp2) / denominator, (d11 * dp2 - d12 * dp1) / denominator)
+}
+
+/* Blends the texture coordinates at the corners, the same way
+ * SmoothTriangle blends its normals
+ */
+fn blend_texture_coords(corners: Option<[(f64, f64); 3]>, u: f64, v: f64) -> Option<(f64, f64)> {
+    let [t1, t2, t3] = corners?;
+    let w = 1.0 - u - v;
+
+    return Some((t1.0 * w + t2.0 * u + t3.0 * v, t1.1 * w + t2.1 * u + t3.1 * v))
+}
+
+fn triangle_bounds(p1: primatives::PointT, p2: primatives::PointT, p3: primatives::PointT,
+transformation: matrix::Matrix4x4) -> bounds::BoundingBox {
+    let obj_bounds = bounds::new_empty().add_point(p1).add_point(p2).add_point(p3);
+    return obj_bounds.transformed(transformation);
+}
+
+fn to_world_normal(transformation: matrix::Matrix4x4, obj_normal: primatives::Vec3T) -> primatives::Vec3T {
+    let mut world_normal = transformation.inverse().transposed() * obj_normal;
+    world_normal.w = 0.0;
+
+    return world_normal.normalized()
+}
+
+impl Triangle {
+    //Texture coordinates at the u and v of an intersection with this triangle
+    pub fn texture_coords_at(&self, u: f64, v: f64) -> Option<(f64, f64)> {
+        return blend_texture_coords(self.texture_coords, u, v);
+    }
+}
+
+impl SmoothTriangle {
+    pub fn texture_coords_at(&self, u: f64, v: f64) -> Option<(f64, f64)> {
+        return blend_texture_coords(self.texture_coords, u, v);
+    }
+}
+
+impl shapes::Shape for Triangle {
+    fn intersect(&self, r_input: ray::Ray) -> Vec<intersection::Intersection<'_>> {
+        let r = self.transformation.inverse() * r_input;
+
+        return match moller_trumbore(self.p1, self.e1, self.e2, r) {
+            Some((t, u, v)) => vec![intersection::new_with_uv(t, r_input.position(t), self, r_input, u, v)],
+            None => vec![]
+        }
+    }
+
+    fn world_to_object(&self, p: primatives::PointT) -> primatives::PointT {
+        return self.transformation.inverse() * p;
+    }
+
+    fn transformation(&self) -> matrix::Matrix4x4 {
+        return self.transformation;
+    }
+
+    fn bounds(&self) -> bounds::BoundingBox {
+        return triangle_bounds(self.p1, self.p2, self.p3, self.transformation);
+    }
+}
+
+impl shapes::Surface for Triangle {
+    fn normal_at(&self, p: primatives::PointT) -> primatives::Vec3T {
+        p.check_type(primatives::TYPE_PNT);
+        return to_world_normal(self.transformation, self.normal);
+    }
+
+    fn get_material(&self) -> material::Material {
+        return self.material.clone();
+    }
+}
+
+impl shapes::Shape for SmoothTriangle {
+    fn intersect(&self, r_input: ray::Ray) -> Vec<intersection::Intersection<'_>> {
+        let r = self.transformation.inverse() * r_input;
+
+        return match moller_trumbore(self.p1, self.e1, self.e2, r) {
+            Some((t, u, v)) => vec![intersection::new_with_uv(t, r_input.position(t), self, r_input, u, v)],
+            None => vec![]
+        }
+    }
+
+    fn world_to_object(&self, p: primatives::PointT) -> primatives::PointT {
+        return self.transformation.inverse() * p;
+    }
+
+    fn transformation(&self) -> matrix::Matrix4x4 {
+        return self.transformation;
+    }
+
+    fn bounds(&self) -> bounds::BoundingBox {
+        return triangle_bounds(self.p1, self.p2, self.p3, self.transformation);
+    }
+}
+
+impl shapes::Surface for SmoothTriangle {
+    //Without u and v from an intersection, they have to be worked out from the point
+    fn normal_at(&self, p: primatives::PointT) -> primatives::Vec3T {
+        p.check_type(primatives::TYPE_PNT);
+
+        let (u, v) = barycentric(self.p1, self.e1, self.e2, self.transformation.inverse() * p);
+        return self.normal_at_uv(p, u, v);
+    }
+
+    fn normal_at_uv(&self, p: primatives::PointT, u: f64, v: f64) -> primatives::Vec3T {
+        p.check_type(primatives::TYPE_PNT);
+
+        let obj_normal = self.n2 * u + self.n3 * v + self.n1 * (1.0 - u - v);
+        return to_world_normal(self.transformation, obj_normal);
+    }
+
+    fn get_material(&self) -> material::Material {
+        return self.material.clone();
+    }
+}
@@ -0,0 +1,123 @@
+use crate::color;
+use crate::random;
+
+/* Turns the linear light a render adds up (where anything can be brighter
+ * than 1.0) into values for an 8 bit image. The steps, in order:
+ *
+ * exposure: scales the light by 2^exposure, ie, +1 is twice as bright.
+ * operator: squeezes bright values into 0.0 to 1.0. Clamp just cuts them
+ *   off, Reinhard rolls them off gently, and Aces and Filmic are film-like
+ *   curves that also add a little contrast to the midtones.
+ * srgb: screens expect sRGB gamma encoded values, not linear ones. Without
+ *   it, midtones come out too dark.
+ * dither: adds a tiny bit of noise before rounding to 8 bits, so smooth
+ *   gradients do not show bands.
+ */
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Operator {
+    Clamp,
+    Reinhard,
+    Aces,
+    Filmic
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ToneMap {
+    pub exposure: f64,
+    pub operator: Operator,
+    pub srgb: bool,
+    pub dither: bool
+}
+
+//Leaves colors as they are, only clamping them. This is what canvases use by default
+pub fn new_linear() -> ToneMap {
+    return ToneMap {
+        exposure: 0.0,
+        operator: Operator::Clamp,
+        srgb: false,
+        dither: false
+    }
+}
+
+pub fn new(exposure: f64, operator: Operator) -> ToneMap {
+    return ToneMap {
+        exposure: exposure,
+        operator: operator,
+        srgb: true,
+        dither: false
+    }
+}
+
+//Krzysztof Narkowicz's fit of the ACES filmic curve
+fn aces(x: f64) -> f64 {
+    return (x * (2.51 * x + 0.03)) / (x * (2.43 * x + 0.59) + 0.14);
+}
+
+//John Hable's curve from Uncharted 2
+fn hable(x: f64) -> f64 {
+    let (a, b, c, d, e, f) = (0.15, 0.50, 0.10, 0.20, 0.02, 0.30);
+    return ((x * (a * x + c * b) + d * e) / (x * (a * x + b) + d * f)) - e / f;
+}
+
+//The linear value that Hable's curve maps to white
+const FILMIC_WHITE: f64 = 11.2;
+
+pub fn srgb_encode(linear: f64) -> f64 {
+    return
+        if linear <= 0.0031308 {
+            12.92 * linear
+        } else {
+            //Same as 1.055 * p - 0.055, but gives exactly 1.0 for white
+            let p = linear.powf(1.0 / 2.4);
+            p + 0.055 * (p - 1.0)
+        };
+}
+
+impl ToneMap {
+    fn map_channel(&self, value: f64) -> f64 {
+        let v = (value * 2.0_f64.powf(self.exposure)).max(0.0);
+
+        let mapped = match self.operator {
+            Operator::Clamp => v,
+            Operator::Reinhard => v / (1.0 + v),
+            Operator::Aces => aces(v),
+            Operator::Filmic => hable(v * 2.0) / hable(FILMIC_WHITE)
+        };
+        let mapped = mapped.clamp(0.0, 1.0);
+
+        return
+            if self.srgb {
+                srgb_encode(mapped)
+            } else {
+                mapped
+            };
+    }
+
+    //The display color, between 0.0 and 1.0, for a linear color
+    pub fn apply(&self, c: color::Color) -> color::Color {
+        return color::new(self.map_channel(c.red), self.map_channel(c.green), self.map_channel(c.blue));
+    }
+
+    /* The color as 8 bit red, green and blue. (x, y) is where the pixel is,
+     * which seeds the dither so the same image always gives the same bytes
+     */
+    pub fn to_rgb8(&self, c: color::Color, x: usize, y: usize) -> [u8; 3] {
+        let display = self.apply(c);
+        let channels = [display.red, display.green, display.blue];
+
+        let mut ret_val = [0; 3];
+        for i in 0..3 {
+            let offset =
+                if self.dither {
+                    random::jitter(&[x as u64, y as u64, i as u64])
+                } else {
+                    0.0
+                };
+
+            ret_val[i] = (channels[i] * 255.0 + offset).floor().min(255.0) as u8;
+        }
+
+        return ret_val
+    }
+}
@@ -7,50 +7,246 @@ use crate::ray;
 use crate::shading;
 use crate::camera;
 use crate::canvas;
+use crate::bvh;
+
+use std::sync::Mutex;
+use std::thread;
 
 pub struct World<'a> {
     pub objects: Vec<&'a (dyn Shape + 'a)>,
-    pub light: light::Light,
-    pub camera: camera::Camera
+    pub lights: Vec<light::Light>,
+    pub camera: camera::Camera,
+
+    /* How many times a ray may bounce between reflective surfaces.
+     * Without a limit, two facing mirrors would recurse forever
+     */
+    pub max_recursion_depth: usize,
+
+    /* Speeds up intersect() when there are many objects. It is built by
+     * build_bvh(), and has to be rebuilt whenever objects are changed.
+     * If objects no longer holds exactly the shapes it was built from
+     * (any added, removed or replaced), it is ignored
+     */
+    pub bvh: Option<bvh::Bvh>
 }
 
+pub const DEFAULT_MAX_RECURSION_DEPTH: usize = 5;
+
 pub fn new<'a>(c: camera::Camera) -> World<'a> {
     return World {
         objects: vec![],
-        light: light::new(color::new(1.0, 1.0, 1.0), primatives::point(-10.0, 10.0, -10.0)),
-        camera: c
+        lights: vec![light::new(color::new(1.0, 1.0, 1.0), primatives::point(-10.0, 10.0, -10.0))],
+        camera: c,
+        max_recursion_depth: DEFAULT_MAX_RECURSION_DEPTH,
+        bvh: None
     }
 }
 
 impl World<'_> {
+    pub fn add_light(&mut self, l: light::Light) {
+        self.lights.push(l);
+    }
+
+    //Removes and returns the light at the given index
+    pub fn remove_light(&mut self, index: usize) -> light::Light {
+        return self.lights.remove(index);
+    }
+
+    pub fn build_bvh(&mut self) {
+        self.bvh = Some(bvh::build(&self.objects));
+    }
+
     pub fn intersect<'a>(&'a self, r: ray::Ray) -> Vec<intersection::Intersection<'a>> {
         let mut ret_val = vec![];
 
-        for obj in &self.objects {
-            ret_val.extend(obj.intersect(r));
+        match &self.bvh {
+            Some(b) if b.built_from(&self.objects) => {
+                for i in b.candidates(r) {
+                    ret_val.extend(self.objects[i].intersect(r));
+                }
+            },
+            _ => {
+                for obj in &self.objects {
+                    ret_val.extend(obj.intersect(r));
+                }
+            }
         }
 
         ret_val.sort_by(|a, b| a.time.partial_cmp(&b.time).unwrap());
         return ret_val
     }
 
-    pub fn color_at_ray<'a>(&'a self, r: ray::Ray) -> color::Color {
+    pub fn color_at_ray(&self, r: ray::Ray) -> color::Color {
+        return self.color_at_ray_with_depth(r, self.max_recursion_depth);
+    }
+
+    //remaining_depth is the number of further bounces the ray is allowed
+    pub fn color_at_ray_with_depth(&self, r: ray::Ray, remaining_depth: usize) -> color::Color {
         let intersections = self.intersect(r);
-        if intersections.len() == 0 {
-            return color::new(0.0, 0.0, 0.0)
-        } 
-            
-        return shading::shade_intersection(intersections[0], self.light)
+        let hit = match intersection::hit(&intersections) {
+            Some(i) => intersection::refractive_indices(i, &intersections),
+            None => return color::new(0.0, 0.0, 0.0)
+        };
+
+        /* Each light contributes separately, and may be blocked separately.
+         * Area lights are shaded one sample at a time, so a point that can
+         * only see part of the light ends up in a soft penumbra
+         */
+        let mut surface = color::BLACK;
+        for l in &self.lights {
+            for sample in l.samples(hit.over_point) {
+                let in_shadow = self.is_shadowed(hit.over_point, sample);
+                surface = surface + shading::shade_intersection(hit, sample, in_shadow);
+            }
+        }
+
+        let reflected = self.reflected_color(hit, remaining_depth);
+        let refracted = self.refracted_color(hit, remaining_depth);
+
+        //Surfaces that are both reflective and transparent (eg, glass) split
+        //the light between the two according to the Fresnel effect
+        let mtrl = hit.shape.get_material();
+        if mtrl.reflective > 0.0 && mtrl.transparency > 0.0 {
+            let reflectance = shading::schlick(hit);
+            return surface + reflected * reflectance + refracted * (1.0 - reflectance)
+        }
+
+        return surface + reflected + refracted
+    }
+
+    /* Follows the ray as it bounces off of the surface, and scales
+     * whatever it sees by how reflective the surface is
+     */
+    pub fn reflected_color(&self, i: intersection::Intersection, remaining_depth: usize) -> color::Color {
+        let reflective = i.shape.get_material().reflective;
+        if reflective == 0.0 || remaining_depth == 0 {
+            return color::BLACK
+        }
+
+        let r = ray::new(i.over_point, i.reflectv);
+        return self.color_at_ray_with_depth(r, remaining_depth - 1) * reflective
     }
 
+    /* Bends the ray as it passes through the surface, according to
+     * Snell's law: sin(theta_i) / sin(theta_t) = n2 / n1. The intersection
+     * must have had its n1 and n2 filled in by refractive_indices()
+     */
+    pub fn refracted_color(&self, i: intersection::Intersection, remaining_depth: usize) -> color::Color {
+        let transparency = i.shape.get_material().transparency;
+        if transparency == 0.0 || remaining_depth == 0 {
+            return color::BLACK
+        }
+
+        let eyev = i.eyev.normalized();
+        let n_ratio = i.n1 / i.n2;
+        let cos_i = primatives::dot_product(eyev, i.normalv);
+        let sin2_t = n_ratio * n_ratio * (1.0 - cos_i * cos_i);
+
+        //Past the critical angle, all light is reflected and none passes through
+        if sin2_t > 1.0 {
+            return color::BLACK
+        }
+
+        let cos_t = (1.0 - sin2_t).sqrt();
+        let direction = i.normalv * (n_ratio * cos_i - cos_t) - eyev * n_ratio;
+
+        let r = ray::new(i.under_point, direction);
+        return self.color_at_ray_with_depth(r, remaining_depth - 1) * transparency
+    }
+
+    /* Casts a ray from the point towards a light. If anything is hit
+     * before the ray reaches the light, the point is in shadow.
+     */
+    pub fn is_shadowed(&self, p: primatives::PointT, l: light::Light) -> bool {
+        p.check_type(primatives::TYPE_PNT);
+
+        let distance = l.distance_from(p);
+        let r = ray::new(p, l.direction_from(p));
+
+        return match intersection::hit(&self.intersect(r)) {
+            Some(i) => i.time < distance,
+            None => false
+        }
+    }
+
+    /* Casts every sub-pixel ray the camera asks for, and combines them
+     * with the camera's filter. With the default of one ray, that is just
+     * the color through the center of the pixel
+     */
+    pub fn color_at_pixel(&self, x: usize, y: usize) -> color::Color {
+        let mut total = color::BLACK;
+        let mut total_weight = 0.0;
+
+        for (x_offset, y_offset, weight) in self.camera.pixel_samples(x, y) {
+            let r = self.camera.ray_at_pixel(x, y, x_offset, y_offset);
+            total = total + self.color_at_ray(r) * weight;
+            total_weight += weight;
+        }
+
+        //Negative filter lobes could cancel out everything, in theory
+        if total_weight <= 0.0 {
+            return color::BLACK;
+        }
+
+        return total * (1.0 / total_weight);
+    }
+
+    //Renders using every core available
     pub fn render_to_canvas(&self) -> canvas::Canvas {
+        let threads = match thread::available_parallelism() {
+            Ok(n) => n.get(),
+            Err(_) => 1
+        };
+
+        return self.render_to_canvas_with_threads(threads);
+    }
+
+    /* Rows of the canvas are handed out one at a time to whichever thread
+     * is free, so threads that get cheap rows (eg, empty sky) do not sit
+     * idle. Every pixel is computed exactly as it is on a single thread,
+     * so the image does not depend on the number of threads
+     */
+    pub fn render_to_canvas_with_threads(&self, threads: usize) -> canvas::Canvas {
+        if threads <= 1 {
+            return self.render_to_canvas_serial();
+        }
+
+        let mut c = canvas::new(self.camera.width, self.camera.height);
+        c.origin = (0, 0);
+
+        if c.width == 0 || c.height == 0 {
+            return c;
+        }
+
+        let width = c.width;
+        let rows = Mutex::new(c.contents.chunks_mut(width).enumerate());
+
+        thread::scope(|s| {
+            for _ in 0..threads {
+                s.spawn(|| loop {
+                    let next_row = rows.lock().unwrap().next();
+                    let (canvas_y_coord, row) = match next_row {
+                        Some(r) => r,
+                        None => break
+                    };
+
+                    for (canvas_x_coord, pixel) in row.iter_mut().enumerate() {
+                        *pixel = self.color_at_pixel(canvas_x_coord, canvas_y_coord);
+                    }
+                });
+            }
+        });
+
+        return c;
+    }
+
+    pub fn render_to_canvas_serial(&self) -> canvas::Canvas {
         let mut c = canvas::new(self.camera.width, self.camera.height);
         c.origin = (0, 0);
 
         for canvas_x_coord in 0..c.width {
             for canvas_y_coord in 0..c.height {
-                let r = self.camera.ray_at_pixel(canvas_x_coord, canvas_y_coord);
-                let color = self.color_at_ray(r);
+                let color = self.color_at_pixel(canvas_x_coord, canvas_y_coord);
                 c.plot(canvas_x_coord as i32, canvas_y_coord as i32, color);
             }
         }
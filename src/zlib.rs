@@ -0,0 +1,192 @@
+/* Just enough of zlib (RFC 1950) and deflate (RFC 1951) to write PNG
+ * files. Repeated runs of bytes are found with LZ77 (a hash of the next
+ * three bytes points at earlier places they were seen), and the result
+ * is packed with deflate's fixed Huffman codes. That is not the smallest
+ * possible output, but it needs no code tables in the file, and rendered
+ * images (big flat areas, smooth gradients after PNG filtering) shrink a lot
+ */
+
+const WINDOW_SIZE: usize = 32768;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+
+//How many earlier places to check for each match. More is smaller and slower
+const MAX_CHAIN: usize = 64;
+
+const HASH_BITS: usize = 15;
+
+//(first length, extra bits) for length codes 257 to 285
+const LENGTH_CODES: [(usize, u32); 29] = [
+    (3, 0), (4, 0), (5, 0), (6, 0), (7, 0), (8, 0), (9, 0), (10, 0),
+    (11, 1), (13, 1), (15, 1), (17, 1), (19, 2), (23, 2), (27, 2), (31, 2),
+    (35, 3), (43, 3), (51, 3), (59, 3), (67, 4), (83, 4), (99, 4), (115, 4),
+    (131, 5), (163, 5), (195, 5), (227, 5), (258, 0)
+];
+
+//(first distance, extra bits) for distance codes 0 to 29
+const DISTANCE_CODES: [(usize, u32); 30] = [
+    (1, 0), (2, 0), (3, 0), (4, 0), (5, 1), (7, 1), (9, 2), (13, 2),
+    (17, 3), (25, 3), (33, 4), (49, 4), (65, 5), (97, 5), (129, 6), (193, 6),
+    (257, 7), (385, 7), (513, 8), (769, 8), (1025, 9), (1537, 9), (2049, 10), (3073, 10),
+    (4097, 11), (6145, 11), (8193, 12), (12289, 12), (16385, 13), (24577, 13)
+];
+
+//Deflate packs bits starting from the lowest bit of each byte
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u64,
+    count: u32
+}
+
+impl BitWriter {
+    fn write_bits(&mut self, value: u32, bits: u32) {
+        self.buffer |= (value as u64) << self.count;
+        self.count += bits;
+
+        while self.count >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    //Huffman codes are the one thing written highest bit first
+    fn write_code(&mut self, code: u32, bits: u32) {
+        let reversed = code.reverse_bits() >> (32 - bits);
+        self.write_bits(reversed, bits);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+
+        return self.bytes
+    }
+
+    //Literal bytes, the end of block marker (256), and match lengths (257+)
+    fn write_symbol(&mut self, symbol: usize) {
+        let s = symbol as u32;
+        match symbol {
+            0..=143 => self.write_code(0b00110000 + s, 8),
+            144..=255 => self.write_code(0b110010000 + (s - 144), 9),
+            256..=279 => self.write_code(s - 256, 7),
+            _ => self.write_code(0b11000000 + (s - 280), 8)
+        }
+    }
+
+    fn write_match(&mut self, length: usize, distance: usize) {
+        let code = LENGTH_CODES.iter().rposition(|(first, _)| *first <= length).unwrap();
+        let (first, extra) = LENGTH_CODES[code];
+        self.write_symbol(257 + code);
+        self.write_bits((length - first) as u32, extra);
+
+        let code = DISTANCE_CODES.iter().rposition(|(first, _)| *first <= distance).unwrap();
+        let (first, extra) = DISTANCE_CODES[code];
+        self.write_code(code as u32, 5);
+        self.write_bits((distance - first) as u32, extra);
+    }
+}
+
+fn hash3(data: &[u8], i: usize) -> usize {
+    let v = ((data[i] as u32) << 16) | ((data[i + 1] as u32) << 8) | (data[i + 2] as u32);
+    return (v.wrapping_mul(2654435761) >> (32 - HASH_BITS)) as usize;
+}
+
+//Remembers that the three bytes at i were seen here
+fn insert(data: &[u8], head: &mut [usize], prev: &mut [usize], i: usize) {
+    if i + MIN_MATCH <= data.len() {
+        let h = hash3(data, i);
+        prev[i] = head[h];
+        head[h] = i;
+    }
+}
+
+//Compresses data into a single, final, fixed Huffman deflate block
+pub fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut out = BitWriter { bytes: vec![], buffer: 0, count: 0 };
+
+    //BFINAL = 1, BTYPE = 01 (fixed Huffman codes)
+    out.write_bits(1, 1);
+    out.write_bits(1, 2);
+
+    /* head holds the latest position each hash was seen at, and prev
+     * links every position back to the one before it with the same hash.
+     * usize::MAX means none
+     */
+    let mut head = vec![usize::MAX; 1 << HASH_BITS];
+    let mut prev = vec![usize::MAX; data.len()];
+
+    let mut i = 0;
+    while i < data.len() {
+        let mut best_length = 0;
+        let mut best_distance = 0;
+
+        if i + MIN_MATCH <= data.len() {
+            let max_length = MAX_MATCH.min(data.len() - i);
+            let mut candidate = head[hash3(data, i)];
+            let mut chain = 0;
+
+            while candidate != usize::MAX && i - candidate <= WINDOW_SIZE && chain < MAX_CHAIN {
+                let mut length = 0;
+                while length < max_length && data[candidate + length] == data[i + length] {
+                    length += 1;
+                }
+
+                if length > best_length {
+                    best_length = length;
+                    best_distance = i - candidate;
+                    if length == max_length {
+                        break;
+                    }
+                }
+
+                candidate = prev[candidate];
+                chain += 1;
+            }
+        }
+
+        if best_length >= MIN_MATCH {
+            out.write_match(best_length, best_distance);
+            for j in i..(i + best_length) {
+                insert(data, &mut head, &mut prev, j);
+            }
+            i += best_length;
+        } else {
+            out.write_symbol(data[i] as usize);
+            insert(data, &mut head, &mut prev, i);
+            i += 1;
+        }
+    }
+
+    //End of block
+    out.write_symbol(256);
+    return out.finish()
+}
+
+pub fn adler32(data: &[u8]) -> u32 {
+    let mut a: u32 = 1;
+    let mut b: u32 = 0;
+
+    //5552 is the most bytes that can be summed before b could overflow
+    for chunk in data.chunks(5552) {
+        for byte in chunk {
+            a += *byte as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+
+    return (b << 16) | a
+}
+
+//A zlib stream: a two byte header, the deflated data, then a checksum
+pub fn compress(data: &[u8]) -> Vec<u8> {
+    //32K window, deflate, and a check value that makes the header a multiple of 31
+    let mut ret_val = vec![0x78, 0x01];
+    ret_val.extend(deflate(data));
+    ret_val.extend(adler32(data).to_be_bytes());
+
+    return ret_val
+}
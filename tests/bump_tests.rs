@@ -0,0 +1,93 @@
+#[cfg(test)]
+
+use frog::primatives;
+use frog::color;
+use frog::bump;
+use frog::ray;
+use frog::patterns::gradient;
+use frog::shapes::plane;
+use frog::shapes::sphere;
+use frog::shapes::intersection;
+use frog::shapes::{Shape};
+use frog::matrix::transformations;
+
+#[test]
+fn noise_bump() {
+    let up = primatives::vec3(0.0, 1.0, 0.0);
+    let b = bump::new_noise(4.0, 0.3, 2);
+
+    let p1 = primatives::point(0.3, 0.0, 0.7);
+    let p2 = primatives::point(0.6, 0.0, 0.2);
+    let n1 = b.perturb(p1, up);
+    let n2 = b.perturb(p2, up);
+
+    assert_eq!(n1, b.perturb(p1, up));
+    assert!(n1 != up);
+    assert!(n1 != n2);
+    assert!((n1.magnitude() - 1.0).abs() < 0.0001);
+    assert!(primatives::dot_product(n1, up) > 0.8);
+
+    //Nothing to tip by
+    let flat = bump::new_noise(4.0, 0.0, 2);
+    assert_eq!(flat.perturb(p1, up), up);
+}
+
+#[test]
+fn height_map_bump() {
+    let up = primatives::vec3(0.0, 1.0, 0.0);
+
+    //A ramp that rises towards +x, so the normal tips back towards -x
+    let ramp = gradient::new(color::BLACK, color::new(1.0, 1.0, 1.0));
+    let b = bump::new_height_map(ramp, 0.5);
+    let n = b.perturb(primatives::point(0.5, 0.0, 0.5), up);
+    assert_eq!(n, primatives::vec3(-0.5, 1.0, 0.0).normalized());
+
+    //Slopes straight along the normal do not tip it
+    let side = primatives::vec3(1.0, 0.0, 0.0);
+    assert_eq!(b.perturb(primatives::point(0.5, 0.0, 0.5), side), side);
+}
+
+#[test]
+fn bumped_intersections() {
+    let mut water = plane::new();
+    water.material.bump = Some(bump::new_noise(2.0, 0.2, 3));
+
+    let r = ray::new(primatives::point(0.3, 1.0, 0.2), primatives::vec3(0.0, -1.0, 0.0));
+    let i = water.intersect(r)[0];
+    assert!(i.normalv != primatives::vec3(0.0, 1.0, 0.0));
+    assert_eq!(i.inside, false);
+    assert_eq!(i.reflectv, i.normalv.reflect(r.direction));
+
+    //Offsets still use the flat surface
+    assert_eq!(i.over_point.y, intersection::OVER_POINT_EPSILON);
+    assert_eq!(i.under_point.y, -intersection::OVER_POINT_EPSILON);
+
+    //However far the bumps tip the normal, it still faces the eye
+    let mut rough = sphere::new(1.0, primatives::point(0.0, 0.0, 0.0));
+    rough.material.bump = Some(bump::new_noise(8.0, 5.0, 1));
+    for step in 0..100 {
+        let x = step as f64 / 101.0;
+        let r = ray::new(primatives::point(x, 0.0, -5.0), primatives::vec3(0.0, 0.0, 1.0));
+        for i in rough.intersect(r) {
+            assert!(primatives::dot_product(i.normalv, i.eyev) >= 0.0);
+        }
+    }
+}
+
+#[test]
+fn bumps_turn_with_the_shape() {
+    let ramp = gradient::new(color::BLACK, color::new(1.0, 1.0, 1.0));
+
+    let mut floor = plane::new();
+    floor.material.bump = Some(bump::new_height_map(ramp.clone(), 0.5));
+    let r = ray::new(primatives::point(0.5, 1.0, 0.5), primatives::vec3(0.0, -1.0, 0.0));
+    let i = floor.intersect(r)[0];
+    assert_eq!(i.normalv, primatives::vec3(-0.5, 1.0, 0.0).normalized());
+
+    //Stood up on its side, the plane faces +x and the bump is turned the same way
+    let mut wall = transformations::new_rotation_z_matrix(-transformations::PI / 2.0) * plane::new();
+    wall.material.bump = Some(bump::new_height_map(ramp, 0.5));
+    let r = ray::new(primatives::point(1.0, 0.5, 0.5), primatives::vec3(-1.0, 0.0, 0.0));
+    let i = wall.intersect(r)[0];
+    assert_eq!(i.normalv, primatives::vec3(1.0, 0.5, 0.0).normalized());
+}
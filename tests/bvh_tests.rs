@@ -0,0 +1,229 @@
+#[cfg(test)]
+
+use frog::bvh;
+use frog::shapes::bounds;
+use frog::shapes::sphere;
+use frog::shapes::plane;
+use frog::shapes::{Shape};
+use frog::primatives;
+use frog::ray;
+use frog::matrix::transformations;
+use frog::world;
+use frog::camera;
+use frog::color;
+
+#[test]
+fn bounding_box_creation() {
+    let b = bounds::new(primatives::point(-1.0, -2.0, -3.0), primatives::point(3.0, 2.0, 1.0));
+    assert_eq!(b.centroid(), primatives::point(1.0, 0.0, -1.0));
+    assert_eq!(b.surface_area(), 2.0 * (4.0 * 4.0 + 4.0 * 4.0 + 4.0 * 4.0));
+    assert_eq!(b.is_infinite(), false);
+
+    let b = bounds::new_empty().add_point(primatives::point(1.0, 2.0, 3.0));
+    assert_eq!(b.min, primatives::point(1.0, 2.0, 3.0));
+    assert_eq!(b.max, primatives::point(1.0, 2.0, 3.0));
+    assert_eq!(bounds::new_empty().surface_area(), 0.0);
+
+    let b = b.merge(bounds::new(primatives::point(-1.0, 5.0, 0.0), primatives::point(0.0, 6.0, 1.0)));
+    assert_eq!(b.min, primatives::point(-1.0, 2.0, 0.0));
+    assert_eq!(b.max, primatives::point(1.0, 6.0, 3.0));
+
+    assert_eq!(bounds::new_infinite().is_infinite(), true);
+    assert_eq!(bounds::new_infinite().transformed(transformations::new_rotation_x_matrix(1.0)).is_infinite(), true);
+}
+
+#[test]
+fn bounding_box_transformation() {
+    let b = bounds::new(primatives::point(-1.0, -1.0, -1.0), primatives::point(1.0, 1.0, 1.0));
+
+    let t = transformations::new_translation_matrix(1.0, 2.0, 3.0);
+    let moved = b.transformed(t);
+    assert_eq!(moved.min, primatives::point(0.0, 1.0, 2.0));
+    assert_eq!(moved.max, primatives::point(2.0, 3.0, 4.0));
+
+    //A rotated cube needs a bigger box to fit in
+    let rotated = b.transformed(transformations::new_rotation_y_matrix(transformations::PI / 4.0));
+    let half_diagonal = 2.0_f64.sqrt();
+    assert_eq!(rotated.min, primatives::point(-half_diagonal, -1.0, -half_diagonal));
+    assert_eq!(rotated.max, primatives::point(half_diagonal, 1.0, half_diagonal));
+}
+
+#[test]
+fn ray_bounding_box_intersection() {
+    let b = bounds::new(primatives::point(-1.0, -1.0, -1.0), primatives::point(1.0, 1.0, 1.0));
+
+    let hits = [
+        ray::new(primatives::point(5.0, 0.5, 0.0), primatives::vec3(-1.0, 0.0, 0.0)),
+        ray::new(primatives::point(0.0, 0.0, -5.0), primatives::vec3(0.0, 0.0, 1.0)),
+        ray::new(primatives::point(0.0, 0.0, 0.0), primatives::vec3(0.0, 1.0, 0.0)),
+        ray::new(primatives::point(-5.0, -5.0, -5.0), primatives::vec3(1.0, 1.0, 1.0)),
+        //The box is behind the ray, which still counts
+        ray::new(primatives::point(0.0, 0.0, 5.0), primatives::vec3(0.0, 0.0, 1.0)),
+    ];
+    for r in hits {
+        assert_eq!(b.intersects(r), true);
+    }
+
+    let misses = [
+        ray::new(primatives::point(-2.0, 0.0, 0.0), primatives::vec3(0.2673, 0.5345, 0.8018)),
+        ray::new(primatives::point(2.0, 2.0, 0.0), primatives::vec3(0.0, 0.0, 1.0)),
+        ray::new(primatives::point(0.0, 2.0, 2.0), primatives::vec3(0.0, 0.0, -1.0)),
+        ray::new(primatives::point(2.0, 0.0, 2.0), primatives::vec3(-1.0, 0.0, 0.0)),
+    ];
+    for r in misses {
+        assert_eq!(b.intersects(r), false);
+    }
+
+    //Infinite boxes are hit by everything
+    let r = ray::new(primatives::point(100.0, 2.0, 3.0), primatives::vec3(0.0, 0.0, 1.0));
+    assert_eq!(bounds::new_infinite().intersects(r), true);
+}
+
+#[test]
+fn shape_bounds() {
+    let s = sphere::new(2.0, primatives::point(1.0, 0.0, 0.0));
+    let b = s.bounds();
+    assert_eq!(b.min, primatives::point(-1.0, -2.0, -2.0));
+    assert_eq!(b.max, primatives::point(3.0, 2.0, 2.0));
+
+    let s = transformations::new_translation_matrix(0.0, 10.0, 0.0) * sphere::new(1.0, primatives::point(0.0, 0.0, 0.0));
+    let b = s.bounds();
+    assert_eq!(b.min, primatives::point(-1.0, 9.0, -1.0));
+    assert_eq!(b.max, primatives::point(1.0, 11.0, 1.0));
+
+    assert_eq!(plane::new().bounds().is_infinite(), true);
+}
+
+#[test]
+fn bvh_candidates() {
+    let mut spheres = vec![];
+    for i in 0..50 {
+        spheres.push(sphere::new(0.4, primatives::point(i as f64, 0.0, 0.0)));
+    }
+    let floor = transformations::new_translation_matrix(0.0, -1.0, 0.0) * plane::new();
+
+    let mut objects: Vec<&dyn Shape> = vec![];
+    for s in &spheres {
+        objects.push(s);
+    }
+    objects.push(&floor);
+
+    let b = bvh::build(&objects);
+    assert_eq!(b.object_count, 51);
+    assert!(b.depth() > 1);
+
+    //Looking down on a single sphere, only it, its neighbours in the same
+    //leaf and the floor need testing
+    let r = ray::new(primatives::point(7.0, 5.0, 0.0), primatives::vec3(0.0, -1.0, 0.0));
+    let candidates = b.candidates(r);
+    assert!(candidates.contains(&7));
+    assert!(candidates.contains(&50));
+    assert!(candidates.len() <= bvh::MAX_LEAF_SIZE + 1);
+
+    //Looking down the row sees every sphere
+    let r = ray::new(primatives::point(-5.0, 0.0, 0.0), primatives::vec3(1.0, 0.0, 0.0));
+    assert_eq!(b.candidates(r).len(), 51);
+
+    //Nothing but the floor is out here
+    let r = ray::new(primatives::point(0.0, 5.0, 20.0), primatives::vec3(0.0, -1.0, 0.0));
+    assert_eq!(b.candidates(r), vec![50]);
+
+    let empty = bvh::build(&[]);
+    assert_eq!(empty.candidates(r).len(), 0);
+    assert_eq!(empty.depth(), 0);
+}
+
+#[test]
+fn bvh_with_nan_bounds() {
+    //A NaN in the transformation gives bounds that can not be compared
+    let mut spheres = vec![];
+    for i in 0..10 {
+        spheres.push(sphere::new(0.4, primatives::point(i as f64, 0.0, 0.0)));
+    }
+    let mut broken = sphere::new(0.4, primatives::point(0.0, 0.0, 0.0));
+    broken.transformation[0][3] = f64::NAN;
+
+    let mut objects: Vec<&dyn Shape> = vec![&broken];
+    for s in &spheres {
+        objects.push(s);
+    }
+
+    let b = bvh::build(&objects);
+    let r = ray::new(primatives::point(3.0, 5.0, 0.0), primatives::vec3(0.0, -1.0, 0.0));
+    let candidates = b.candidates(r);
+    assert!(candidates.contains(&0));
+    assert!(candidates.contains(&4));
+}
+
+#[test]
+fn bvh_render_matches_and_does_less_work() {
+    let c = camera::new(32, 32, transformations::PI / 3.0);
+    let mut w = world::new(c);
+    w.camera.transformation = transformations::new_view_transformation_matrix(
+        primatives::point(0.0, 25.0, -40.0), primatives::point(0.0, 0.0, 0.0), primatives::vec3(0.0, 1.0, 0.0));
+
+    //A 20 x 20 grid of spheres on a floor
+    let mut spheres = vec![];
+    for x in 0..20 {
+        for z in 0..20 {
+            let mut s = sphere::new(0.5, primatives::point(x as f64 * 2.0 - 19.0, 0.5, z as f64 * 2.0 - 19.0));
+            s.material.color = color::new(x as f64 / 20.0, 0.5, z as f64 / 20.0);
+            spheres.push(s);
+        }
+    }
+    let floor = plane::new();
+
+    for s in &spheres {
+        w.objects.push(s);
+    }
+    w.objects.push(&floor);
+
+    let without_bvh = w.render_to_canvas_serial();
+    w.build_bvh();
+    let with_bvh = w.render_to_canvas_serial();
+    assert_eq!(with_bvh.contents, without_bvh.contents);
+
+    /* Rather than timing the renders (which depends on the machine),
+     * count the shapes rays from the camera have to be tested against.
+     * Without the hierarchy, that is every one of them
+     */
+    let b = bvh::build(&w.objects);
+    let eye = primatives::point(0.0, 25.0, -40.0);
+    let mut tested = 0;
+    let mut rays = 0;
+    for x in -20..20 {
+        for z in -20..20 {
+            let target = primatives::point(x as f64, 0.0, z as f64);
+            tested += b.candidates(ray::new(eye, (target - eye).normalized())).len();
+            rays += 1;
+        }
+    }
+    let untested = rays * w.objects.len();
+    assert!(tested * 4 < untested, "{} of {} shapes tested", tested, untested);
+
+    //A stale hierarchy is ignored, rather than giving wrong answers
+    let extra = sphere::new(1.0, primatives::point(0.0, 30.0, 0.0));
+    w.objects.push(&extra);
+    let r = ray::new(primatives::point(0.0, 40.0, 0.0), primatives::vec3(0.0, -1.0, 0.0));
+    assert_eq!(w.intersect(r)[0].time, 9.0);
+
+    //Replacing an object makes it stale too, even though the count is the same
+    w.build_bvh();
+    let moved = sphere::new(1.0, primatives::point(0.0, 20.0, 0.0));
+    w.objects[0] = &moved;
+    let times: Vec<f64> = w.intersect(r).iter().map(|i| i.time).collect();
+    assert_eq!(times[..3], [9.0, 11.0, 19.0]);
+}
+
+#[test]
+fn empty_bounding_box() {
+    let b = bounds::new_empty();
+    assert_eq!(b.is_empty(), true);
+    assert_eq!(b.is_infinite(), false);
+    assert_eq!(b.transformed(transformations::new_rotation_x_matrix(1.0)).is_empty(), true);
+
+    let r = ray::new(primatives::point(0.0, 0.0, -5.0), primatives::vec3(0.0, 0.0, 1.0));
+    assert_eq!(b.intersects(r), false);
+
+    assert_eq!(b.add_point(primatives::point(0.0, 0.0, 0.0)).is_empty(), false);
+}
@@ -7,6 +7,8 @@ use frog::shapes::sphere;
 use frog::world;
 use frog::color;
 use frog::matrix::transformations;
+use frog::filter;
+use frog::shapes::plane;
 
 #[test]
 fn camera_creation() {
@@ -34,7 +36,7 @@ fn camera_ray_creation() {
     let pi = std::f64::consts::PI;
 
     let c = camera::new(201, 101, pi / 2.0);
-    let r = c.ray_at_pixel(100, 50);
+    let r = c.ray_at_pixel(100, 50, 0.5, 0.5);
 
     let expected_r_origin = primatives::point(0.0, 0.0, 0.0);
     let expected_r_vec = primatives::vec3(0.0, 0.0, -1.0);
@@ -43,7 +45,7 @@ fn camera_ray_creation() {
     assert_eq!(r.direction, expected_r_vec);
 
     let c = camera::new(201, 101, pi / 2.0);
-    let r = c.ray_at_pixel(0, 0);
+    let r = c.ray_at_pixel(0, 0, 0.5, 0.5);
 
     let expected_r_origin = primatives::point(0.0, 0.0, 0.0);
     let expected_r_vec = primatives::vec3(0.66519, 0.33259, -0.66851);
@@ -54,7 +56,7 @@ fn camera_ray_creation() {
     let mut c = camera::new(201, 101, pi / 2.0);
     c.transformation = transformations::new_rotation_y_matrix(pi / 4.0) *
         transformations::new_translation_matrix(0.0, -2.0, 5.0);
-    let r = c.ray_at_pixel(100, 50);
+    let r = c.ray_at_pixel(100, 50, 0.5, 0.5);
 
     let expected_r_origin = primatives::point(0.0, 2.0, -5.0);
     let expected_r_vec = primatives::vec3(2.0_f64.sqrt(), 0.0, -2.0_f64.sqrt());
@@ -65,6 +67,87 @@ fn camera_ray_creation() {
 
 }
 
+#[test]
+fn pixel_samples() {
+    let mut c = camera::new(10, 10, std::f64::consts::PI / 2.0);
+
+    //One ray, through the center
+    assert_eq!(c.sampling, camera::Sampling::Grid);
+    assert_eq!(c.pixel_samples(3, 4), vec![(0.5, 0.5, 1.0)]);
+
+    //A grid
+    c.samples = 2;
+    let samples = c.pixel_samples(3, 4);
+    assert_eq!(samples, vec![(0.25, 0.25, 1.0), (0.25, 0.75, 1.0), (0.75, 0.25, 1.0), (0.75, 0.75, 1.0)]);
+
+    //Each jittered ray stays in its own cell
+    c.sampling = camera::Sampling::Jittered;
+    let jittered = c.pixel_samples(3, 4);
+    assert_eq!(jittered, c.pixel_samples(3, 4));
+    assert!(jittered != c.pixel_samples(4, 3));
+    for (s, j) in samples.iter().zip(jittered.iter()) {
+        assert!((s.0 - j.0).abs() <= 0.25 && (s.1 - j.1).abs() <= 0.25);
+        assert_eq!(j.2, 1.0);
+    }
+
+    //Wider filters reach into the neighboring pixels
+    c.sampling = camera::Sampling::Grid;
+    c.samples = 32;
+    c.filter = filter::Filter::Mitchell;
+    let samples = c.pixel_samples(3, 4);
+    assert_eq!(samples.len(), 1024);
+    assert!(samples.iter().any(|s| s.0 < 0.0 || s.0 > 1.0));
+    assert!(samples.iter().any(|s| s.2 < 0.0));
+    assert!(samples.iter().map(|s| s.2).sum::<f64>() > 0.0);
+
+    //Offsets move the ray across the pixel
+    let center = c.ray_at_pixel(5, 5, 0.5, 0.5);
+    let corner = c.ray_at_pixel(5, 5, 0.0, 0.0);
+    let next = c.ray_at_pixel(4, 5, 1.0, 0.0);
+    assert!(center.direction != corner.direction);
+    assert_eq!(corner.direction, next.direction);
+}
+
+#[test]
+fn supersampled_edges() {
+    //Looking straight down at a floor that is black on one half and white on the other
+    let mut c = camera::new(5, 5, std::f64::consts::PI / 2.0);
+    c.transformation = transformations::new_view_transformation_matrix(
+        primatives::point(0.0, 1.0, 0.0), primatives::point(0.0, 0.0, 0.0), primatives::vec3(0.0, 0.0, 1.0));
+
+    let mut floor = plane::new();
+    floor.material.pattern = Some(std::sync::Arc::new(frog::patterns::stripe::new(color::new(1.0, 1.0, 1.0), color::BLACK)));
+    floor.material.ambient = 1.0;
+    floor.material.diffuse = 0.0;
+    floor.material.specular = 0.0;
+
+    let mut w = world::new(c);
+    w.objects.push(&floor);
+
+    //The middle column straddles the edge at x = 0. One ray picks a side
+    let one_ray = w.color_at_pixel(2, 2);
+    assert!(one_ray == color::BLACK || one_ray == color::new(1.0, 1.0, 1.0));
+    assert_eq!(one_ray, w.color_at_ray(w.camera.ray_at_pixel(2, 2, 0.5, 0.5)));
+
+    //Many rays land in between
+    w.camera.samples = 4;
+    for f in [filter::Filter::Box, filter::Filter::Tent, filter::Filter::Gaussian, filter::Filter::Mitchell] {
+        w.camera.filter = f;
+        let mixed = w.color_at_pixel(2, 2);
+        assert!(mixed.red > 0.2 && mixed.red < 0.8, "{:?} gave {:?}", f, mixed);
+    }
+
+    //Away from the edge, nothing changes
+    w.camera.filter = filter::Filter::Box;
+    assert_eq!(w.color_at_pixel(0, 2), w.color_at_ray(w.camera.ray_at_pixel(0, 2, 0.5, 0.5)));
+
+    //The same on any number of threads
+    w.camera.sampling = camera::Sampling::Jittered;
+    let serial = w.render_to_canvas_serial();
+    let parallel = w.render_to_canvas_with_threads(3);
+    assert_eq!(serial.contents, parallel.contents);
+}
+
 #[test]
 #[ignore]
 fn draw_world_with_camera() {
@@ -86,7 +169,7 @@ fn draw_world_with_camera() {
     w.objects.push(&s2);
     w.objects.push(&s3);
 
-    w.light.location = primatives::point(-400.0, -400.0, 1000.0);
+    w.lights[0].location = primatives::point(-400.0, -400.0, 1000.0);
 
     let el = primatives::point(0.0, 0.0, 1000.0);
     let pv = primatives::point(0.0, 0.0, 0.0);
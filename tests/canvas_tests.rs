@@ -39,4 +39,80 @@ fn test_plot_out_of_bounds() {
     let mut canvas = canvas::new(100, 100);
     let c1 = color::new(1.0, 0.0, 0.0);
     canvas.plot(50, 50, c1);
-}
\ No newline at end of file
+}
+#[test]
+fn write_formats() {
+    let mut canvas = canvas::new(2, 1);
+    canvas.contents[0] = color::new(1.0, 0.5, 0.0);
+    canvas.contents[1] = color::new(0.0, 0.0, 2.0);
+    assert_eq!(canvas.to_rgb8(), vec![255, 127, 0, 0, 0, 255]);
+
+    let mut p3 = vec![];
+    canvas.write_to(&mut p3, canvas::Format::P3).unwrap();
+    assert_eq!(String::from_utf8(p3).unwrap(), "P3\n2 1\n255\n255 127 0\n0 0 255\n");
+
+    let mut p6 = vec![];
+    canvas.write_to(&mut p6, canvas::Format::P6).unwrap();
+    let mut expected = b"P6\n2 1\n255\n".to_vec();
+    expected.extend([255, 127, 0, 0, 0, 255]);
+    assert_eq!(p6, expected);
+
+    let mut png = vec![];
+    canvas.write_to(&mut png, canvas::Format::Png).unwrap();
+    assert_eq!(png, frog::png::encode(2, 1, &canvas.to_rgb8()));
+
+    //Files get the same bytes
+    let filename = std::env::temp_dir().join("frog_canvas_tests_formats.png");
+    let filename = filename.to_str().unwrap();
+    canvas.write_to_file(filename, canvas::Format::Png).unwrap();
+    assert_eq!(std::fs::read(filename).unwrap(), png);
+    std::fs::remove_file(filename).unwrap();
+
+    assert!(canvas.write_to_file("/this/directory/does/not/exist.ppm", canvas::Format::P6).is_err());
+}
+
+#[test]
+fn write_hdr_formats() {
+    //Top row is over exposed, bottom row is dim
+    let mut canvas = canvas::new(2, 2);
+    canvas.contents[0] = color::new(4.0, 1.0, 0.0);
+    canvas.contents[1] = color::new(1.5, 2.5, 100.0);
+    canvas.contents[2] = color::new(0.25, 0.5, 0.75);
+
+    let mut pfm = vec![];
+    canvas.write_to(&mut pfm, canvas::Format::Pfm).unwrap();
+    let header = b"PF\n2 2\n-1.0\n";
+    assert_eq!(pfm[..header.len()], header[..]);
+    assert_eq!(pfm.len(), header.len() + 2 * 2 * 3 * 4);
+
+    //Floats are little endian, starting from the bottom row
+    let floats: Vec<f32> = pfm[header.len()..].chunks(4).map(|b| f32::from_le_bytes(b.try_into().unwrap())).collect();
+    assert_eq!(floats, vec![0.25, 0.5, 0.75, 0.0, 0.0, 0.0, 4.0, 1.0, 0.0, 1.5, 2.5, 100.0]);
+
+    let mut hdr = vec![];
+    canvas.write_to(&mut hdr, canvas::Format::Hdr).unwrap();
+    let header = b"#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y 2 +X 2\n";
+    assert_eq!(hdr[..header.len()], header[..]);
+
+    let mut expected = header.to_vec();
+    for c in &canvas.contents {
+        expected.extend(c.to_rgbe());
+    }
+    assert_eq!(hdr, expected);
+    assert_eq!(hdr[header.len()..(header.len() + 4)], [128, 32, 0, 131]);
+}
+
+#[test]
+fn write_empty_canvas() {
+    //No pixels, but every format still writes its header
+    let canvas = canvas::new(0, 3);
+    for format in [canvas::Format::P3, canvas::Format::P6, canvas::Format::Png, canvas::Format::Pfm, canvas::Format::Hdr] {
+        let mut out = vec![];
+        canvas.write_to(&mut out, format).unwrap();
+        assert!(!out.is_empty());
+    }
+
+    let mut pfm = vec![];
+    canvas.write_to(&mut pfm, canvas::Format::Pfm).unwrap();
+    assert_eq!(pfm, b"PF\n0 3\n-1.0\n");
+}
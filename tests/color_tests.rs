@@ -74,4 +74,25 @@ fn test_color_to_255() {
     let expected = color::new(255.0, 0.0, 127.0);
 
     assert_eq!(result, expected);
-}
\ No newline at end of file
+}
+#[test]
+fn rgbe_encoding() {
+    assert_eq!(color::new(0.0, 0.0, 0.0).to_rgbe(), [0, 0, 0, 0]);
+    assert_eq!(color::new(1.0, 1.0, 1.0).to_rgbe(), [128, 128, 128, 129]);
+    assert_eq!(color::new(0.5, 0.0, 0.0).to_rgbe(), [128, 0, 0, 128]);
+    assert_eq!(color::new(10.0, 5.0, 0.1).to_rgbe(), [160, 80, 1, 132]);
+    assert_eq!(color::new(-1.0, 0.25, 0.0).to_rgbe(), [0, 128, 0, 127]);
+
+    //Values too bright to store are clamped rather than overflowing the exponent
+    assert_eq!(color::new(f64::INFINITY, 1.0, 0.0).to_rgbe(), [255, 0, 0, 255]);
+    assert_eq!(color::new(1e300, 1e300, 1e300).to_rgbe(), [255, 255, 255, 255]);
+    assert_eq!(color::new(color::MAX_RGBE, 0.0, 0.0).to_rgbe(), [255, 0, 0, 255]);
+    assert_eq!(color::new(f64::NAN, 1.0, 1.0).to_rgbe(), [0, 128, 128, 129]);
+
+    //Decodes back to within about a percent, even far above 1.0
+    for value in [0.001, 0.3, 0.999, 1.0, 7.5, 1234.5] {
+        let rgbe = color::new(value, value, value).to_rgbe();
+        let decoded = (rgbe[0] as f64 + 0.5) / 256.0 * 2.0_f64.powi(rgbe[3] as i32 - 128);
+        assert!((decoded - value).abs() / value < 0.01, "{} came back as {}", value, decoded);
+    }
+}
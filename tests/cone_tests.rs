@@ -0,0 +1,86 @@
+#[cfg(test)]
+
+use frog::primatives;
+use frog::shapes::cone;
+use frog::shapes::{Shape, Surface};
+use frog::ray;
+use frog::matrix;
+use frog::material;
+
+#[test]
+fn cone_creation() {
+    let c = cone::new();
+    assert_eq!(c.transformation, matrix::IDENTITY_MATRIX_4X4);
+    assert_eq!(c.material, material::new_default());
+    assert_eq!(c.minimum, f64::NEG_INFINITY);
+    assert_eq!(c.maximum, f64::INFINITY);
+    assert_eq!(c.closed, false);
+
+    let mut c = cone::new();
+    c.minimum = -1.0;
+    c.maximum = 3.0;
+    let b = c.bounds();
+    assert_eq!(b.min, primatives::point(-3.0, -1.0, -3.0));
+    assert_eq!(b.max, primatives::point(3.0, 3.0, 3.0));
+}
+
+#[test]
+fn ray_cone_intersection() {
+    let c = cone::new();
+
+    let hits = [
+        (primatives::point(0.0, 0.0, -5.0), primatives::vec3(0.0, 0.0, 1.0), 5.0, 5.0),
+        (primatives::point(0.0, 0.0, -5.0), primatives::vec3(1.0, 1.0, 1.0), 8.66025, 8.66025),
+        (primatives::point(1.0, 1.0, -5.0), primatives::vec3(-0.5, -1.0, 1.0), 4.55006, 49.44994),
+    ];
+    for (origin, direction, t1, t2) in hits {
+        let intersections = c.intersect(ray::new(origin, direction.normalized()));
+        assert_eq!(intersections.len(), 2);
+        assert!((intersections[0].time - t1).abs() < 0.0001);
+        assert!((intersections[1].time - t2).abs() < 0.0001);
+    }
+
+    //Parallel to one half of the cone, but still hits the other
+    let r = ray::new(primatives::point(0.0, 0.0, -1.0), primatives::vec3(0.0, 1.0, 1.0).normalized());
+    let intersections = c.intersect(r);
+    assert_eq!(intersections.len(), 1);
+    assert!((intersections[0].time - 0.35355).abs() < 0.0001);
+}
+
+#[test]
+fn capped_cone() {
+    let mut c = cone::new();
+    c.minimum = -0.5;
+    c.maximum = 0.5;
+    c.closed = true;
+
+    let cases = [
+        (primatives::point(0.0, 0.0, -5.0), primatives::vec3(0.0, 1.0, 0.0), 0),
+        (primatives::point(0.0, 0.0, -0.25), primatives::vec3(0.0, 1.0, 1.0), 2),
+        (primatives::point(0.0, 0.0, -0.25), primatives::vec3(0.0, 1.0, 0.0), 4),
+    ];
+    for (origin, direction, count) in cases {
+        assert_eq!(c.intersect(ray::new(origin, direction.normalized())).len(), count);
+    }
+}
+
+#[test]
+fn cone_normal() {
+    let mut c = cone::new();
+
+    let normals = [
+        (primatives::point(1.0, 1.0, 1.0), primatives::vec3(1.0, -2.0_f64.sqrt(), 1.0)),
+        (primatives::point(-1.0, -1.0, 0.0), primatives::vec3(-1.0, 1.0, 0.0)),
+    ];
+    for (p, expected) in normals {
+        let n = c.normal_at(p);
+        n.check_type(primatives::TYPE_VEC);
+        assert_eq!(n, expected.normalized());
+    }
+
+    c.minimum = -1.0;
+    c.maximum = 2.0;
+    c.closed = true;
+    assert_eq!(c.normal_at(primatives::point(0.5, 2.0, 0.5)), primatives::vec3(0.0, 1.0, 0.0));
+    assert_eq!(c.normal_at(primatives::point(0.0, -1.0, 0.5)), primatives::vec3(0.0, -1.0, 0.0));
+}
@@ -0,0 +1,215 @@
+#[cfg(test)]
+
+use frog::primatives;
+use frog::shapes::csg;
+use frog::shapes::sphere;
+use frog::shapes::cube;
+use frog::shapes::group;
+use frog::shapes::intersection;
+use frog::shapes::{Shape};
+use frog::ray;
+use frog::matrix;
+use frog::matrix::transformations;
+use frog::material;
+
+fn unit_sphere() -> sphere::Sphere {
+    return sphere::new(1.0, primatives::point(0.0, 0.0, 0.0));
+}
+
+#[test]
+fn csg_creation() {
+    let s = unit_sphere();
+    let c = cube::new();
+    let combined = csg::new(csg::Operation::Union, &s, &c);
+
+    assert_eq!(combined.operation, csg::Operation::Union);
+    assert!(std::ptr::addr_eq(combined.left, &s));
+    assert!(std::ptr::addr_eq(combined.right, &c));
+    assert_eq!(combined.transformation, matrix::IDENTITY_MATRIX_4X4);
+
+    assert_eq!(combined.includes(&s), true);
+    assert_eq!(combined.includes(&c), true);
+    assert_eq!(combined.includes(&unit_sphere()), false);
+}
+
+#[test]
+fn csg_rules() {
+    use csg::Operation::{Union, Intersection, Difference};
+
+    //(operation, left hit, in left, in right, allowed)
+    let cases = [
+        (Union, true, true, true, false),
+        (Union, true, true, false, true),
+        (Union, true, false, true, false),
+        (Union, true, false, false, true),
+        (Union, false, true, true, false),
+        (Union, false, true, false, false),
+        (Union, false, false, true, true),
+        (Union, false, false, false, true),
+        (Intersection, true, true, true, true),
+        (Intersection, true, true, false, false),
+        (Intersection, true, false, true, true),
+        (Intersection, true, false, false, false),
+        (Intersection, false, true, true, true),
+        (Intersection, false, true, false, true),
+        (Intersection, false, false, true, false),
+        (Intersection, false, false, false, false),
+        (Difference, true, true, true, false),
+        (Difference, true, true, false, true),
+        (Difference, true, false, true, false),
+        (Difference, true, false, false, true),
+        (Difference, false, true, true, true),
+        (Difference, false, true, false, true),
+        (Difference, false, false, true, false),
+        (Difference, false, false, false, false),
+    ];
+
+    for (op, left_hit, in_left, in_right, allowed) in cases {
+        assert_eq!(csg::intersection_allowed(op, left_hit, in_left, in_right), allowed);
+    }
+}
+
+#[test]
+fn filter_intersections() {
+    let s = unit_sphere();
+    let c = cube::new();
+    let r = ray::new(primatives::point(0.0, 0.0, -5.0), primatives::vec3(0.0, 0.0, 1.0));
+
+    //Alternating hits: sphere, cube, sphere, cube
+    let left = vec![
+        intersection::new(3.0, r.position(3.0), &s, r),
+        intersection::new(1.0, r.position(1.0), &s, r),
+    ];
+    let right = vec![
+        intersection::new(2.0, r.position(2.0), &c, r),
+        intersection::new(4.0, r.position(4.0), &c, r),
+    ];
+
+    let cases = [
+        (csg::Operation::Union, [1.0, 4.0]),
+        (csg::Operation::Intersection, [2.0, 3.0]),
+        (csg::Operation::Difference, [1.0, 2.0]),
+    ];
+
+    for (op, expected) in cases {
+        let combined = csg::new(op, &s, &c);
+        let filtered = combined.filter_intersections(left.clone(), right.clone());
+        assert_eq!(filtered.len(), 2);
+        assert_eq!(filtered[0].time, expected[0]);
+        assert_eq!(filtered[1].time, expected[1]);
+    }
+}
+
+#[test]
+fn shape_on_both_sides() {
+    //Each side is tracked on its own, even when both sides are the same shape
+    let s = unit_sphere();
+    let r = ray::new(primatives::point(0.0, 0.0, -5.0), primatives::vec3(0.0, 0.0, 1.0));
+
+    for op in [csg::Operation::Union, csg::Operation::Intersection] {
+        let combined = csg::new(op, &s, &s);
+        let intersections = combined.intersect(r);
+        assert_eq!(intersections.len(), 2);
+        assert_eq!(intersections[0].time, 4.0);
+        assert_eq!(intersections[1].time, 6.0);
+    }
+}
+
+#[test]
+fn ray_csg_intersection() {
+    let s1 = unit_sphere();
+    let c = cube::new();
+    let combined = csg::new(csg::Operation::Union, &s1, &c);
+
+    let r = ray::new(primatives::point(0.0, 2.0, -5.0), primatives::vec3(0.0, 0.0, 1.0));
+    assert_eq!(combined.intersect(r).len(), 0);
+
+    let s2 = transformations::new_translation_matrix(0.0, 0.0, 0.5) * unit_sphere();
+    let combined = csg::new(csg::Operation::Union, &s1, &s2);
+
+    let r = ray::new(primatives::point(0.0, 0.0, -5.0), primatives::vec3(0.0, 0.0, 1.0));
+    let intersections = combined.intersect(r);
+    assert_eq!(intersections.len(), 2);
+    assert_eq!(intersections[0].time, 4.0);
+    assert!(std::ptr::addr_eq(intersections[0].shape, &s1));
+    assert_eq!(intersections[1].time, 6.5);
+    assert!(std::ptr::addr_eq(intersections[1].shape, &s2));
+
+    //A lens, from the overlap of two spheres
+    let lens = csg::new(csg::Operation::Intersection, &s1, &s2);
+    let intersections = lens.intersect(r);
+    assert_eq!(intersections.len(), 2);
+    assert_eq!(intersections[0].time, 4.5);
+    assert_eq!(intersections[1].time, 6.0);
+
+    //A cube with a ball scooped out of its front
+    let scoop = transformations::new_translation_matrix(0.0, 0.0, -1.0) * unit_sphere();
+    let scooped = csg::new(csg::Operation::Difference, &c, &scoop);
+    let intersections = scooped.intersect(r);
+    assert_eq!(intersections.len(), 2);
+    assert_eq!(intersections[0].time, 5.0);
+    assert_eq!(intersections[0].normalv, primatives::vec3(0.0, 0.0, -1.0));
+    assert_eq!(intersections[1].time, 6.0);
+}
+
+#[test]
+fn transformed_and_nested_csg() {
+    let s = unit_sphere();
+    let c = cube::new();
+    let combined = transformations::new_translation_matrix(10.0, 0.0, 0.0) * csg::new(csg::Operation::Difference, &c, &s);
+
+    let b = combined.bounds();
+    assert_eq!(b.min, primatives::point(9.0, -1.0, -1.0));
+    assert_eq!(b.max, primatives::point(11.0, 1.0, 1.0));
+
+    //Looking through the hollowed corner of the cube
+    let r = ray::new(primatives::point(10.9, 10.0, 0.9), primatives::vec3(0.0, -1.0, 0.0));
+    let intersections = combined.intersect(r);
+    assert_eq!(intersections.len(), 2);
+    assert_eq!(intersections[0].location, primatives::point(10.9, 1.0, 0.9));
+
+    let r = ray::new(primatives::point(10.5, 10.0, 0.0), primatives::vec3(0.0, -1.0, 0.0));
+    let intersections = combined.intersect(r);
+    assert_eq!(intersections.len(), 4);
+
+    //A group of shapes on one side still counts as that side
+    let mut g = group::new();
+    g.add_child(&s);
+    let outer = csg::new(csg::Operation::Union, &g, &c);
+    assert_eq!(outer.includes(&s), true);
+
+    let r = ray::new(primatives::point(0.0, 0.0, -5.0), primatives::vec3(0.0, 0.0, 1.0));
+    let intersections = outer.intersect(r);
+    assert_eq!(intersections.len(), 2);
+    assert_eq!(intersections[0].time, 4.0);
+    assert_eq!(intersections[1].time, 6.0);
+}
+
+#[test]
+fn csg_normal() {
+    //A sphere with a cube bitten out of its right side
+    let mut s = unit_sphere();
+    s.material.ambient = 0.5;
+    let c = transformations::new_translation_matrix(1.5, 0.0, 0.0) * cube::new();
+    let combined = transformations::new_translation_matrix(0.0, 0.0, 5.0)
+        * csg::new(csg::Operation::Difference, &s, &c);
+
+    //Each intersection keeps the side that was hit, for its normal and material
+    let r = ray::new(primatives::point(-5.0, 0.0, 5.0), primatives::vec3(1.0, 0.0, 0.0));
+    let intersections = combined.intersect(r);
+    assert_eq!(intersections.len(), 2);
+    assert_eq!(intersections[0].time, 4.0);
+    assert_eq!(intersections[0].normal_at(primatives::point(-1.0, 0.0, 5.0)), primatives::vec3(-1.0, 0.0, 0.0));
+    assert_eq!(intersections[0].shape.get_material().ambient, 0.5);
+    assert_eq!(intersections[1].time, 5.5);
+    assert_eq!(intersections[1].normal_at(primatives::point(0.5, 0.0, 5.0)), primatives::vec3(-1.0, 0.0, 0.0));
+    assert_eq!(intersections[1].shape.get_material(), material::new_default());
+
+    //It matches the normal the intersection worked out
+    let r = ray::new(primatives::point(0.2, 0.1, 0.0), primatives::vec3(0.0, 0.0, 1.0));
+    let intersections = combined.intersect(r);
+    assert_eq!(intersections.len(), 2);
+    for i in intersections {
+        assert_eq!(i.normal_at(i.location), i.normalv * if i.inside { -1.0 } else { 1.0 });
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+
+use frog::primatives;
+use frog::shapes::cube;
+use frog::shapes::{Shape, Surface};
+use frog::ray;
+use frog::matrix;
+use frog::matrix::transformations;
+use frog::material;
+
+#[test]
+fn cube_creation() {
+    let c = cube::new();
+    assert_eq!(c.transformation, matrix::IDENTITY_MATRIX_4X4);
+    assert_eq!(c.material, material::new_default());
+
+    let t = transformations::new_scaling_matrix(2.0, 1.0, 3.0);
+    let c = t * c;
+    assert_eq!(c.transformation, t);
+
+    let b = c.bounds();
+    assert_eq!(b.min, primatives::point(-2.0, -1.0, -3.0));
+    assert_eq!(b.max, primatives::point(2.0, 1.0, 3.0));
+}
+
+#[test]
+fn ray_cube_intersection() {
+    let c = cube::new();
+
+    //(origin, direction, t1, t2), one for each face, and one from inside
+    let hits = [
+        (primatives::point(5.0, 0.5, 0.0), primatives::vec3(-1.0, 0.0, 0.0), 4.0, 6.0),
+        (primatives::point(-5.0, 0.5, 0.0), primatives::vec3(1.0, 0.0, 0.0), 4.0, 6.0),
+        (primatives::point(0.5, 5.0, 0.0), primatives::vec3(0.0, -1.0, 0.0), 4.0, 6.0),
+        (primatives::point(0.5, -5.0, 0.0), primatives::vec3(0.0, 1.0, 0.0), 4.0, 6.0),
+        (primatives::point(0.5, 0.0, 5.0), primatives::vec3(0.0, 0.0, -1.0), 4.0, 6.0),
+        (primatives::point(0.5, 0.0, -5.0), primatives::vec3(0.0, 0.0, 1.0), 4.0, 6.0),
+        (primatives::point(0.0, 0.5, 0.0), primatives::vec3(0.0, 0.0, 1.0), -1.0, 1.0),
+    ];
+
+    for (origin, direction, t1, t2) in hits {
+        let intersections = c.intersect(ray::new(origin, direction));
+        assert_eq!(intersections.len(), 2);
+        assert_eq!(intersections[0].time, t1);
+        assert_eq!(intersections[1].time, t2);
+    }
+
+    let misses = [
+        (primatives::point(-2.0, 0.0, 0.0), primatives::vec3(0.2673, 0.5345, 0.8018)),
+        (primatives::point(0.0, -2.0, 0.0), primatives::vec3(0.8018, 0.2673, 0.5345)),
+        (primatives::point(0.0, 0.0, -2.0), primatives::vec3(0.5345, 0.8018, 0.2673)),
+        (primatives::point(2.0, 0.0, 2.0), primatives::vec3(0.0, 0.0, -1.0)),
+        (primatives::point(0.0, 2.0, 2.0), primatives::vec3(0.0, -1.0, 0.0)),
+        (primatives::point(2.0, 2.0, 0.0), primatives::vec3(-1.0, 0.0, 0.0)),
+    ];
+
+    for (origin, direction) in misses {
+        assert_eq!(c.intersect(ray::new(origin, direction)).len(), 0);
+    }
+
+    //A box stretched out along x and moved away
+    let c = transformations::new_translation_matrix(10.0, 0.0, 0.0) * transformations::new_scaling_matrix(3.0, 1.0, 1.0) * cube::new();
+    let intersections = c.intersect(ray::new(primatives::point(0.0, 0.0, 0.0), primatives::vec3(1.0, 0.0, 0.0)));
+    assert_eq!(intersections.len(), 2);
+    assert_eq!(intersections[0].location, primatives::point(7.0, 0.0, 0.0));
+    assert_eq!(intersections[1].location, primatives::point(13.0, 0.0, 0.0));
+}
+
+#[test]
+fn cube_normal() {
+    let c = cube::new();
+
+    let normals = [
+        (primatives::point(1.0, 0.5, -0.8), primatives::vec3(1.0, 0.0, 0.0)),
+        (primatives::point(-1.0, -0.2, 0.9), primatives::vec3(-1.0, 0.0, 0.0)),
+        (primatives::point(-0.4, 1.0, -0.1), primatives::vec3(0.0, 1.0, 0.0)),
+        (primatives::point(0.3, -1.0, -0.7), primatives::vec3(0.0, -1.0, 0.0)),
+        (primatives::point(-0.6, 0.3, 1.0), primatives::vec3(0.0, 0.0, 1.0)),
+        (primatives::point(0.4, 0.4, -1.0), primatives::vec3(0.0, 0.0, -1.0)),
+        //Corners pick one of their faces
+        (primatives::point(1.0, 1.0, 1.0), primatives::vec3(1.0, 0.0, 0.0)),
+        (primatives::point(-1.0, -1.0, -1.0), primatives::vec3(-1.0, 0.0, 0.0)),
+    ];
+
+    for (p, expected) in normals {
+        let n = c.normal_at(p);
+        n.check_type(primatives::TYPE_VEC);
+        assert_eq!(n, expected);
+    }
+
+    let c = transformations::new_scaling_matrix(1.0, 5.0, 1.0) * cube::new();
+    assert_eq!(c.normal_at(primatives::point(0.5, 5.0, 0.5)), primatives::vec3(0.0, 1.0, 0.0));
+}
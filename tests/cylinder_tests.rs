@@ -0,0 +1,120 @@
+#[cfg(test)]
+
+use frog::primatives;
+use frog::shapes::cylinder;
+use frog::shapes::{Shape, Surface};
+use frog::ray;
+use frog::matrix;
+use frog::matrix::transformations;
+use frog::material;
+
+#[test]
+fn cylinder_creation() {
+    let c = cylinder::new();
+    assert_eq!(c.transformation, matrix::IDENTITY_MATRIX_4X4);
+    assert_eq!(c.material, material::new_default());
+    assert_eq!(c.minimum, f64::NEG_INFINITY);
+    assert_eq!(c.maximum, f64::INFINITY);
+    assert_eq!(c.closed, false);
+    assert_eq!(c.bounds().is_infinite(), true);
+
+    let mut c = transformations::new_translation_matrix(0.0, 1.0, 0.0) * c;
+    c.minimum = 1.0;
+    c.maximum = 2.0;
+    let b = c.bounds();
+    assert_eq!(b.min, primatives::point(-1.0, 2.0, -1.0));
+    assert_eq!(b.max, primatives::point(1.0, 3.0, 1.0));
+}
+
+#[test]
+fn ray_cylinder_intersection() {
+    let c = cylinder::new();
+
+    let misses = [
+        (primatives::point(1.0, 0.0, 0.0), primatives::vec3(0.0, 1.0, 0.0)),
+        (primatives::point(0.0, 0.0, 0.0), primatives::vec3(0.0, 1.0, 0.0)),
+        (primatives::point(0.0, 0.0, -5.0), primatives::vec3(1.0, 1.0, 1.0)),
+    ];
+    for (origin, direction) in misses {
+        assert_eq!(c.intersect(ray::new(origin, direction.normalized())).len(), 0);
+    }
+
+    let hits = [
+        (primatives::point(1.0, 0.0, -5.0), primatives::vec3(0.0, 0.0, 1.0), 5.0, 5.0),
+        (primatives::point(0.0, 0.0, -5.0), primatives::vec3(0.0, 0.0, 1.0), 4.0, 6.0),
+        (primatives::point(0.5, 0.0, -5.0), primatives::vec3(0.1, 1.0, 1.0), 6.80798, 7.08872),
+    ];
+    for (origin, direction, t1, t2) in hits {
+        let intersections = c.intersect(ray::new(origin, direction.normalized()));
+        assert_eq!(intersections.len(), 2);
+        assert!((intersections[0].time - t1).abs() < 0.0001);
+        assert!((intersections[1].time - t2).abs() < 0.0001);
+    }
+}
+
+#[test]
+fn truncated_cylinder() {
+    let mut c = cylinder::new();
+    c.minimum = 1.0;
+    c.maximum = 2.0;
+
+    let cases = [
+        (primatives::point(0.0, 1.5, 0.0), primatives::vec3(0.1, 1.0, 0.0), 0),
+        (primatives::point(0.0, 3.0, -5.0), primatives::vec3(0.0, 0.0, 1.0), 0),
+        (primatives::point(0.0, 0.0, -5.0), primatives::vec3(0.0, 0.0, 1.0), 0),
+        (primatives::point(0.0, 2.0, -5.0), primatives::vec3(0.0, 0.0, 1.0), 0),
+        (primatives::point(0.0, 1.0, -5.0), primatives::vec3(0.0, 0.0, 1.0), 0),
+        (primatives::point(0.0, 1.5, -2.0), primatives::vec3(0.0, 0.0, 1.0), 2),
+    ];
+    for (origin, direction, count) in cases {
+        assert_eq!(c.intersect(ray::new(origin, direction.normalized())).len(), count);
+    }
+
+    //Capped
+    c.closed = true;
+    let cases = [
+        (primatives::point(0.0, 3.0, 0.0), primatives::vec3(0.0, -1.0, 0.0), 2),
+        (primatives::point(0.0, 3.0, -2.0), primatives::vec3(0.0, -1.0, 2.0), 2),
+        (primatives::point(0.0, 4.0, -2.0), primatives::vec3(0.0, -1.0, 1.0), 2),
+        (primatives::point(0.0, 0.0, -2.0), primatives::vec3(0.0, 1.0, 2.0), 2),
+        (primatives::point(0.0, -1.0, -2.0), primatives::vec3(0.0, 1.0, 1.0), 2),
+    ];
+    for (origin, direction, count) in cases {
+        assert_eq!(c.intersect(ray::new(origin, direction.normalized())).len(), count);
+    }
+}
+
+#[test]
+fn cylinder_normal() {
+    let c = cylinder::new();
+
+    let normals = [
+        (primatives::point(1.0, 0.0, 0.0), primatives::vec3(1.0, 0.0, 0.0)),
+        (primatives::point(0.0, 5.0, -1.0), primatives::vec3(0.0, 0.0, -1.0)),
+        (primatives::point(0.0, -2.0, 1.0), primatives::vec3(0.0, 0.0, 1.0)),
+        (primatives::point(-1.0, 1.0, 0.0), primatives::vec3(-1.0, 0.0, 0.0)),
+    ];
+    for (p, expected) in normals {
+        let n = c.normal_at(p);
+        n.check_type(primatives::TYPE_VEC);
+        assert_eq!(n, expected);
+    }
+
+    //Normals on the caps
+    let mut c = cylinder::new();
+    c.minimum = 1.0;
+    c.maximum = 2.0;
+    c.closed = true;
+
+    let normals = [
+        (primatives::point(0.0, 1.0, 0.0), primatives::vec3(0.0, -1.0, 0.0)),
+        (primatives::point(0.5, 1.0, 0.0), primatives::vec3(0.0, -1.0, 0.0)),
+        (primatives::point(0.0, 1.0, 0.5), primatives::vec3(0.0, -1.0, 0.0)),
+        (primatives::point(0.0, 2.0, 0.0), primatives::vec3(0.0, 1.0, 0.0)),
+        (primatives::point(0.5, 2.0, 0.0), primatives::vec3(0.0, 1.0, 0.0)),
+        (primatives::point(0.0, 2.0, 0.5), primatives::vec3(0.0, 1.0, 0.0)),
+    ];
+    for (p, expected) in normals {
+        assert_eq!(c.normal_at(p), expected);
+    }
+}
@@ -0,0 +1,53 @@
+#[cfg(test)]
+
+use frog::filter::{Filter};
+
+const FILTERS: [Filter; 4] = [Filter::Box, Filter::Tent, Filter::Gaussian, Filter::Mitchell];
+
+#[test]
+fn filter_curves() {
+    assert_eq!(Filter::Box.weight(0.3), 1.0);
+    assert_eq!(Filter::Box.weight(0.6), 0.0);
+    assert_eq!(Filter::Tent.weight(0.25), 0.75);
+    assert_eq!(Filter::Tent.weight(-0.25), 0.75);
+    assert_eq!(Filter::Gaussian.weight(1.5), 0.0);
+    assert!(Filter::Gaussian.weight(0.0) > Filter::Gaussian.weight(0.5));
+
+    assert!((Filter::Mitchell.weight(0.0) - 0.88889).abs() < 0.0001);
+    assert!((Filter::Mitchell.weight(1.0) - 0.05556).abs() < 0.0001);
+    assert!(Filter::Mitchell.weight(1.5) < 0.0);
+    assert!(Filter::Mitchell.weight(2.0).abs() < 0.0001);
+
+    for f in FILTERS {
+        assert_eq!(f.weight(f.radius() + 0.01), 0.0);
+    }
+}
+
+#[test]
+fn filter_sampling() {
+    for f in FILTERS {
+        //The middle of the range lands on the center, and the ends on the edges
+        assert!(f.sample(0.5).0.abs() < 0.001);
+        assert!((f.sample(0.0).0 + f.radius()).abs() < 0.001);
+        assert!((f.sample(1.0).0 - f.radius()).abs() < 0.001);
+
+        //Steadily increasing, and always within reach of the curve
+        let mut last = -f.radius();
+        for i in 0..=100 {
+            let (x, sign) = f.sample(i as f64 / 100.0);
+            assert!(x >= last && x <= f.radius());
+            assert_eq!(sign, if f.weight(x) < 0.0 { -1.0 } else { 1.0 });
+            last = x;
+        }
+    }
+
+    //Box spreads samples evenly, the others bunch them up near the center
+    assert_eq!(Filter::Box.sample(0.25).0, -0.25);
+    assert!((Filter::Tent.sample(0.125).0 + 0.5).abs() < 0.0001);
+    assert!(Filter::Gaussian.sample(0.25).0 > -0.4);
+
+    //Only Mitchell has negative parts, out near its edges
+    assert_eq!(Filter::Mitchell.sample(0.01).1, -1.0);
+    assert_eq!(Filter::Mitchell.sample(0.5).1, 1.0);
+    assert_eq!(Filter::Gaussian.sample(0.01).1, 1.0);
+}
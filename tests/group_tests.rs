@@ -0,0 +1,190 @@
+#[cfg(test)]
+
+use frog::primatives;
+use frog::shapes::group;
+use frog::shapes::sphere;
+use frog::shapes::plane;
+use frog::shapes::{Shape};
+use frog::shapes::intersection;
+use frog::ray;
+use frog::matrix;
+use frog::matrix::transformations;
+use frog::world;
+use frog::camera;
+use frog::color;
+
+fn unit_sphere() -> sphere::Sphere {
+    return sphere::new(1.0, primatives::point(0.0, 0.0, 0.0));
+}
+
+#[test]
+fn group_creation() {
+    let g = group::new();
+    assert_eq!(g.transformation, matrix::IDENTITY_MATRIX_4X4);
+    assert_eq!(g.children().len(), 0);
+    assert_eq!(g.bounds().is_empty(), true);
+
+    let s = unit_sphere();
+    let mut g = transformations::new_translation_matrix(0.0, 5.0, 0.0) * group::new();
+    g.add_child(&s);
+    assert_eq!(g.children().len(), 1);
+
+    let b = g.bounds();
+    assert_eq!(b.min, primatives::point(-1.0, 4.0, -1.0));
+    assert_eq!(b.max, primatives::point(1.0, 6.0, 1.0));
+
+    let floor = plane::new();
+    g.add_child(&floor);
+    assert_eq!(g.bounds().is_infinite(), true);
+}
+
+#[test]
+fn ray_group_intersection() {
+    let g = group::new();
+    let r = ray::new(primatives::point(0.0, 0.0, 0.0), primatives::vec3(0.0, 0.0, 1.0));
+    assert_eq!(g.intersect(r).len(), 0);
+
+    let s1 = unit_sphere();
+    let s2 = transformations::new_translation_matrix(0.0, 0.0, -3.0) * unit_sphere();
+    let s3 = transformations::new_translation_matrix(5.0, 0.0, 0.0) * unit_sphere();
+    let mut g = group::new();
+    g.add_child(&s1);
+    g.add_child(&s2);
+    g.add_child(&s3);
+
+    let c = camera::new(0, 0, 0.0);
+    let mut w = world::new(c);
+    w.objects.push(&g);
+
+    let r = ray::new(primatives::point(0.0, 0.0, -5.0), primatives::vec3(0.0, 0.0, 1.0));
+    let intersections = w.intersect(r);
+    assert_eq!(intersections.len(), 4);
+    assert_eq!(intersections[0].time, 1.0);
+    assert_eq!(intersections[1].time, 3.0);
+    assert_eq!(intersections[2].time, 4.0);
+    assert_eq!(intersections[3].time, 6.0);
+
+    //The child that was hit is kept, so it can be shaded with its own material
+    assert!(std::ptr::addr_eq(intersections[0].shape, &s2));
+    assert!(std::ptr::addr_eq(intersections[2].shape, &s1));
+
+    //The group's transformation is applied on top of the child's
+    let s = transformations::new_translation_matrix(5.0, 0.0, 0.0) * unit_sphere();
+    let mut g = transformations::new_scaling_matrix(2.0, 2.0, 2.0) * group::new();
+    g.add_child(&s);
+
+    let r = ray::new(primatives::point(10.0, 0.0, -10.0), primatives::vec3(0.0, 0.0, 1.0));
+    let intersections = g.intersect(r);
+    assert_eq!(intersections.len(), 2);
+    assert_eq!(intersections[0].location, primatives::point(10.0, 0.0, 2.0));
+    assert_eq!(intersections[1].location, primatives::point(10.0, 0.0, -2.0));
+    assert_eq!(intersections[1].normalv, primatives::vec3(0.0, 0.0, -1.0));
+
+    //Missing the group's bounds misses everything in it
+    let r = ray::new(primatives::point(0.0, 0.0, -10.0), primatives::vec3(0.0, 0.0, 1.0));
+    assert_eq!(g.intersect(r).len(), 0);
+}
+
+#[test]
+fn nested_group_normal() {
+    let s = transformations::new_translation_matrix(5.0, 0.0, 0.0) * unit_sphere();
+    let mut g2 = transformations::new_scaling_matrix(1.0, 2.0, 3.0) * group::new();
+    g2.add_child(&s);
+    let mut g1 = transformations::new_rotation_y_matrix(transformations::PI / 2.0) * group::new();
+    g1.add_child(&g2);
+
+    //Shoot a ray straight at a known point on the sphere
+    let p = primatives::point(1.7321, 1.1547, -5.5774);
+    let expected = primatives::vec3(0.2857, 0.4286, -0.8571);
+    let r = ray::new(p + expected * 10.0, -expected);
+
+    let intersections = g1.intersect(r);
+    assert_eq!(intersections.len(), 2);
+
+    let hit = if intersections[0].time < intersections[1].time { intersections[0] } else { intersections[1] };
+    assert_eq!(hit.location, p);
+    assert_eq!(hit.normalv, expected);
+    assert_eq!(hit.eyev, expected);
+    assert_eq!(hit.inside, false);
+    assert_eq!(hit.reflectv, expected);
+}
+
+#[test]
+fn instanced_groups() {
+    //One model, placed twice
+    let mut seat = transformations::new_scaling_matrix(1.0, 0.2, 1.0) * unit_sphere();
+    seat.material.color = color::new(0.6, 0.3, 0.1);
+
+    let mut left = transformations::new_translation_matrix(-3.0, 0.0, 0.0) * group::new();
+    left.add_child(&seat);
+    let mut right = transformations::new_translation_matrix(3.0, 0.0, 0.0) * group::new();
+    right.add_child(&seat);
+
+    let c = camera::new(0, 0, 0.0);
+    let mut w = world::new(c);
+    w.objects.push(&left);
+    w.objects.push(&right);
+    w.lights[0].location = primatives::point(0.0, 10.0, 0.0);
+
+    let down = primatives::vec3(0.0, -1.0, 0.0);
+    let on_left = w.color_at_ray(ray::new(primatives::point(-3.0, 5.0, 0.0), down));
+    let on_right = w.color_at_ray(ray::new(primatives::point(3.0, 5.0, 0.0), down));
+    let between = w.color_at_ray(ray::new(primatives::point(0.0, 5.0, 0.0), down));
+
+    assert!(on_left.red > 0.0);
+    assert_eq!(on_left, on_right);
+    assert_eq!(between, color::new(0.0, 0.0, 0.0));
+
+    //Groups work with the BVH as well
+    w.build_bvh();
+    assert_eq!(w.color_at_ray(ray::new(primatives::point(3.0, 5.0, 0.0), down)), on_right);
+}
+
+#[test]
+fn group_normal() {
+    //The normal is worked out through every group between the child and the world
+    let s = transformations::new_translation_matrix(5.0, 0.0, 0.0) * unit_sphere();
+    let mut g2 = transformations::new_scaling_matrix(1.0, 2.0, 3.0) * group::new();
+    g2.add_child(&s);
+    let mut g1 = transformations::new_rotation_y_matrix(transformations::PI / 2.0) * group::new();
+    g1.add_child(&g2);
+
+    let p = primatives::point(1.7321, 1.1547, -5.5774);
+    let intersections = g1.intersect(ray::new(primatives::point(p.x, p.y, -20.0), primatives::vec3(0.0, 0.0, 1.0)));
+    assert_eq!(intersections.len(), 2);
+    assert_eq!(intersections[0].normal_at(p), primatives::vec3(0.2857, 0.4286, -0.8571));
+    assert_eq!(intersections[0].world_to_object(p), primatives::point(0.5774, 0.5774, 0.5774));
+
+    //The same child in another group is moved by that group instead
+    let mut g3 = transformations::new_translation_matrix(0.0, 0.0, 10.0) * group::new();
+    g3.add_child(&s);
+    let i = intersection::hit(&g3.intersect(ray::new(primatives::point(5.0, 0.0, 0.0), primatives::vec3(0.0, 0.0, 1.0)))).unwrap();
+    assert_eq!(i.time, 9.0);
+    assert_eq!(i.normal_at(primatives::point(5.0, 0.0, 9.0)), primatives::vec3(0.0, 0.0, -1.0));
+
+    //It matches the normal the intersection worked out
+    let r = ray::new(primatives::point(0.3, 1.2, -10.0), primatives::vec3(0.0, 0.0, 1.0));
+    for i in g1.intersect(r).iter().chain(g3.intersect(r).iter()) {
+        assert_eq!(i.normal_at(i.location), i.normalv * if i.inside { -1.0 } else { 1.0 });
+    }
+}
+
+#[test]
+fn group_material() {
+    //Each intersection is shaded with the material of the child that was hit
+    let mut s1 = unit_sphere();
+    s1.material.ambient = 0.5;
+    let mut s2 = transformations::new_translation_matrix(0.0, 0.0, -3.0) * unit_sphere();
+    s2.material.ambient = 0.25;
+    let mut g = group::new();
+    g.add_child(&s1);
+    g.add_child(&s2);
+
+    let r = ray::new(primatives::point(0.0, 0.0, -10.0), primatives::vec3(0.0, 0.0, 1.0));
+    let intersections = g.intersect(r);
+    assert_eq!(intersections.len(), 4);
+    for i in intersections {
+        let expected = if i.time < 8.5 { 0.25 } else { 0.5 };
+        assert_eq!(i.shape.get_material().ambient, expected);
+    }
+}
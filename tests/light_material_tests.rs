@@ -6,7 +6,7 @@ use frog::light;
 use frog::material;
 use frog::shading;
 use frog::shapes;
-use frog::shapes::{Shape};
+use frog::shapes::{Shape, Surface};
 use frog::ray;
 use frog::matrix::transformations;
 use frog::canvas;
@@ -21,6 +21,197 @@ fn light_creation() {
     assert_eq!(light.location, l);
 }
 
+#[test]
+fn area_light_creation() {
+    let i = color::new(1.0, 1.0, 1.0);
+    let corner = primatives::point(0.0, 0.0, 0.0);
+    let uvec = primatives::vec3(2.0, 0.0, 0.0);
+    let vvec = primatives::vec3(0.0, 0.0, 1.0);
+
+    let light = light::new_area(i, corner, uvec, vvec, 4);
+    assert_eq!(light.intensity, i);
+    assert_eq!(light.location, primatives::point(1.0, 0.0, 0.5));
+    assert_eq!(light.kind, light::LightKind::Area { corner: corner, uvec: uvec, vvec: vvec, samples: 4 });
+
+    //A point light is its own only sample
+    let point_light = light::new(i, primatives::point(0.0, 0.0, 0.0));
+    assert_eq!(point_light.kind, light::LightKind::Point);
+    assert_eq!(point_light.samples(primatives::point(1.0, 1.0, 1.0)), vec![point_light]);
+}
+
+#[test]
+fn area_light_samples() {
+    let i = color::new(1.0, 1.0, 1.0);
+    let light = light::new_area(i, primatives::point(0.0, 0.0, 0.0),
+        primatives::vec3(2.0, 0.0, 0.0), primatives::vec3(0.0, 0.0, 1.0), 4);
+
+    let lit_point = primatives::point(0.0, -5.0, 0.0);
+    let samples = light.samples(lit_point);
+    assert_eq!(samples.len(), 16);
+
+    //Together, the samples give off as much light as the whole light
+    let mut total = color::new(0.0, 0.0, 0.0);
+    for s in &samples {
+        total = total + s.intensity;
+    }
+    assert_eq!(total, i);
+
+    //Each sample is jittered within its own cell of the rectangle
+    for u in 0..4 {
+        for v in 0..4 {
+            let s = samples[u * 4 + v].location;
+            assert!(s.x >= u as f64 * 0.5 && s.x < (u + 1) as f64 * 0.5);
+            assert!(s.z >= v as f64 * 0.25 && s.z < (v + 1) as f64 * 0.25);
+            assert_eq!(s.y, 0.0);
+        }
+    }
+
+    //The same point always sees the same samples, but other points do not
+    assert_eq!(light.samples(lit_point), samples);
+    assert!(light.samples(primatives::point(0.1, -5.0, 0.0)) != samples);
+}
+
+#[test]
+#[should_panic]
+fn area_light_without_samples() {
+    light::new_area(color::new(1.0, 1.0, 1.0), primatives::point(0.0, 0.0, 0.0),
+        primatives::vec3(1.0, 0.0, 0.0), primatives::vec3(0.0, 1.0, 0.0), 0);
+}
+
+#[test]
+fn directional_light() {
+    let i = color::new(1.0, 1.0, 1.0);
+    let light = light::new_directional(i, primatives::vec3(0.0, -2.0, 0.0));
+    assert_eq!(light.kind, light::LightKind::Directional { direction: primatives::vec3(0.0, -1.0, 0.0) });
+
+    //Rays are parallel, do not fall off, and never reach the light
+    let near = primatives::point(0.0, 0.0, 0.0);
+    let far = primatives::point(500.0, -1000.0, 20.0);
+    assert_eq!(light.direction_from(near), primatives::vec3(0.0, 1.0, 0.0));
+    assert_eq!(light.direction_from(far), primatives::vec3(0.0, 1.0, 0.0));
+    assert_eq!(light.intensity_at(near), i);
+    assert_eq!(light.intensity_at(far), i);
+    assert_eq!(light.distance_from(far), f64::INFINITY);
+
+    //Shades just like a point light straight along the same direction
+    let m = material::new_default();
+    let eyev = primatives::vec3(0.0, 0.0, -1.0);
+    let normalv = primatives::vec3(0.0, 0.0, -1.0);
+    let sun = light::new_directional(i, primatives::vec3(0.0, 0.0, 1.0));
+    let result = shading::shade(&m, sun, far, far, eyev, normalv, false);
+    assert_eq!(result, color::new(1.9, 1.9, 1.9));
+}
+
+#[test]
+fn spot_light() {
+    let i = color::new(1.0, 1.0, 1.0);
+    let pi = std::f64::consts::PI;
+    let light = light::new_spot(i, primatives::point(0.0, 10.0, 0.0), primatives::vec3(0.0, -1.0, 0.0),
+        pi / 8.0, pi / 4.0);
+
+    assert_eq!(light.direction_from(primatives::point(0.0, 0.0, 0.0)), primatives::vec3(0.0, 1.0, 0.0));
+    assert_eq!(light.distance_from(primatives::point(0.0, 0.0, 0.0)), 10.0);
+
+    //Inside the inner cone, at full strength
+    assert_eq!(light.intensity_at(primatives::point(0.0, 0.0, 0.0)), i);
+    assert_eq!(light.intensity_at(primatives::point(3.0, 0.0, 0.0)), i);
+
+    //Outside the outer cone, nothing
+    assert_eq!(light.intensity_at(primatives::point(11.0, 0.0, 0.0)), color::new(0.0, 0.0, 0.0));
+    assert_eq!(light.intensity_at(primatives::point(0.0, 20.0, 0.0)), color::new(0.0, 0.0, 0.0));
+
+    //Between the cones, it fades out smoothly
+    let mut last = 1.0;
+    for x in 5..10 {
+        let c = light.intensity_at(primatives::point(x as f64, 0.0, 0.0));
+        assert!(c.red > 0.0 && c.red < last);
+        last = c.red;
+    }
+
+    //Outside the cone, a surface does not even get ambient light
+    let m = material::new_default();
+    let eyev = primatives::vec3(0.0, 1.0, 0.0);
+    let normalv = primatives::vec3(0.0, 1.0, 0.0);
+    let result = shading::shade(&m, light, primatives::point(20.0, 0.0, 0.0), primatives::point(20.0, 0.0, 0.0), eyev, normalv, false);
+    assert_eq!(result, color::new(0.0, 0.0, 0.0));
+}
+
+#[test]
+#[should_panic]
+fn directional_light_without_direction() {
+    light::new_directional(color::new(1.0, 1.0, 1.0), primatives::vec3(0.0, 0.0, 0.0));
+}
+
+#[test]
+#[should_panic]
+fn spot_light_without_direction() {
+    light::new_spot(color::new(1.0, 1.0, 1.0), primatives::point(0.0, 0.0, 0.0),
+        primatives::vec3(0.0, 0.0, 0.0), 0.1, 0.2);
+}
+
+#[test]
+#[should_panic]
+fn spot_light_inside_out() {
+    let pi = std::f64::consts::PI;
+    light::new_spot(color::new(1.0, 1.0, 1.0), primatives::point(0.0, 0.0, 0.0),
+        primatives::vec3(0.0, -1.0, 0.0), pi / 4.0, pi / 8.0);
+}
+
+#[test]
+fn light_attenuation() {
+    let i = color::new(1.0, 1.0, 1.0);
+    let mut light = light::new(i, primatives::point(0.0, 0.0, -10.0));
+    assert_eq!(light.attenuation, light::Attenuation::None);
+    assert_eq!(light.attenuation_at(primatives::point(0.0, 0.0, 0.0)), 1.0);
+
+    light.attenuation = light::Attenuation::InverseSquare;
+    assert_eq!(light.attenuation_at(primatives::point(0.0, 0.0, 0.0)), 0.01);
+    assert_eq!(light.attenuation_at(primatives::point(0.0, 0.0, 10.0)), 0.0025);
+
+    //Right next to the light it is not brighter than the light itself
+    assert_eq!(light.attenuation_at(primatives::point(0.0, 0.0, -10.0)), 1.0);
+    assert_eq!(light.attenuation_at(primatives::point(0.0, 0.0, -9.5)), 1.0);
+
+    light.attenuation = light::new_attenuation(1.0, 0.1, 0.01);
+    match light.attenuation {
+        light::Attenuation::Polynomial(p) => assert_eq!(p.coefficients(), (1.0, 0.1, 0.01)),
+        other => panic!("Expected a polynomial, got {:?}", other)
+    }
+    assert_eq!(light.attenuation_at(primatives::point(0.0, 0.0, 0.0)), 1.0 / 3.0);
+    assert_eq!(light.attenuation_at(primatives::point(0.0, 0.0, -10.0)), 1.0);
+
+    //Without a constant term it stays finite at the light
+    light.attenuation = light::new_attenuation(0.0, 1.0, 0.0);
+    assert_eq!(light.attenuation_at(primatives::point(0.0, 0.0, -10.0)), 1.0 / light::ATTENUATION_EPSILON);
+
+    //Directional lights are too far away to fall off
+    let mut sun = light::new_directional(i, primatives::vec3(0.0, -1.0, 0.0));
+    sun.attenuation = light::Attenuation::InverseSquare;
+    assert_eq!(sun.attenuation_at(primatives::point(0.0, 0.0, 0.0)), 1.0);
+
+    //Diffuse and specular are dimmed, but ambient is not
+    let m = material::new_default();
+    let eyev = primatives::vec3(0.0, 0.0, -1.0);
+    let normalv = primatives::vec3(0.0, 0.0, -1.0);
+    light.attenuation = light::new_attenuation(2.0, 0.0, 0.0);
+    let result = shading::shade(&m, light, primatives::point(0.0, 0.0, 0.0), primatives::point(0.0, 0.0, 0.0), eyev, normalv, false);
+    assert_eq!(result, color::new(0.1 + 1.8 / 2.0, 0.1 + 1.8 / 2.0, 0.1 + 1.8 / 2.0));
+
+    //Area light samples keep the attenuation of the whole light
+    let mut area = light::new_area(i, primatives::point(0.0, 0.0, 0.0),
+        primatives::vec3(1.0, 0.0, 0.0), primatives::vec3(0.0, 1.0, 0.0), 2);
+    area.attenuation = light::Attenuation::InverseSquare;
+    for s in area.samples(primatives::point(0.0, 0.0, -1.0)) {
+        assert_eq!(s.attenuation, light::Attenuation::InverseSquare);
+    }
+}
+
+#[test]
+#[should_panic]
+fn attenuation_all_zero() {
+    light::new_attenuation(0.0, 0.0, 0.0);
+}
+
 #[test]
 fn material_creation() {
     let m = material::new_default();
@@ -29,6 +220,9 @@ fn material_creation() {
     assert_eq!(m.diffuse, 0.9);
     assert_eq!(m.specular, 0.9);
     assert_eq!(m.shininess, 200.0);
+    assert_eq!(m.reflective, 0.0);
+    assert_eq!(m.transparency, 0.0);
+    assert_eq!(m.refractive_index, 1.0);
 }
 
 #[test]
@@ -44,7 +238,7 @@ fn lighting_test() {
     let normal_vec = primatives::vec3(0.0, 0.0, -1.0); //placeholder; in a real scene this will be calculted off a shape
     let light = light::new(color::new(1.0, 1.0, 1.0), primatives::point(0.0, 0.0, -10.0)); //Light is behind eye
 
-    let result = shading::shade(material_for_item, light, location_to_be_lit, vec_from_eye_to_location, normal_vec);
+    let result = shading::shade(&material_for_item, light, location_to_be_lit, location_to_be_lit, vec_from_eye_to_location, normal_vec, false);
     let expected = color::new(1.9, 1.9, 1.9);
     assert_eq!(expected, result);
 
@@ -53,7 +247,7 @@ fn lighting_test() {
     let normalv = primatives::vec3(0.0, 0.0, -1.0);
     let light = light::new(color::new(1.0, 1.0, 1.0), primatives::point(0.0, 0.0, -10.0));
 
-    let result = shading::shade(material_for_item, light, location_to_be_lit, eyev, normalv);
+    let result = shading::shade(&material_for_item, light, location_to_be_lit, location_to_be_lit, eyev, normalv, false);
     let expected = color::new(1.0, 1.0, 1.0);
     assert_eq!(result, expected);
 
@@ -62,7 +256,7 @@ fn lighting_test() {
     let normalv = primatives::vec3(0.0, 0.0, -1.0);
     let light = light::new(color::new(1.0, 1.0, 1.0), primatives::point(0.0, 10.0, -10.0));
 
-    let result = shading::shade(material_for_item, light, location_to_be_lit, eyev, normalv);
+    let result = shading::shade(&material_for_item, light, location_to_be_lit, location_to_be_lit, eyev, normalv, false);
     let expected = color::new(0.7364, 0.7364, 0.7364);
     assert_eq!(result, expected);
 
@@ -71,7 +265,7 @@ fn lighting_test() {
     let normalv = primatives::vec3(0.0, 0.0, -1.0);
     let light = light::new(color::new(1.0, 1.0, 1.0), primatives::point(0.0, 10.0, -10.0));
 
-    let result = shading::shade(material_for_item, light, location_to_be_lit, eyev, normalv);
+    let result = shading::shade(&material_for_item, light, location_to_be_lit, location_to_be_lit, eyev, normalv, false);
     let expected = color::new(1.6364, 1.6364, 1.6364);
     assert_eq!(result, expected);
 
@@ -80,11 +274,18 @@ fn lighting_test() {
     let normalv = primatives::vec3(0.0, 0.0, -1.0);
     let light = light::new(color::new(1.0, 1.0, 1.0), primatives::point(0.0, 0.0, 10.0));
 
-    let result = shading::shade(material_for_item, light, location_to_be_lit, eyev, normalv);
+    let result = shading::shade(&material_for_item, light, location_to_be_lit, location_to_be_lit, eyev, normalv, false);
     let expected = color::new(0.1, 0.1, 0.1);
     assert_eq!(result, expected);
 
-    
+    //When the point is in shadow, only ambient light remains
+    let eyev = primatives::vec3(0.0, 0.0, -1.0);
+    let normalv = primatives::vec3(0.0, 0.0, -1.0);
+    let light = light::new(color::new(1.0, 1.0, 1.0), primatives::point(0.0, 0.0, -10.0));
+
+    let result = shading::shade(&material_for_item, light, location_to_be_lit, location_to_be_lit, eyev, normalv, true);
+    let expected = color::new(0.1, 0.1, 0.1);
+    assert_eq!(result, expected);
 }
 
 #[test]
@@ -117,7 +318,7 @@ fn draw_sphere_shaded() {
                 let normalv = s.normal_at(pnt);
                 let eyev = vantage_point - pnt;
 
-                let c1 = shading::shade(s.material, light, pnt, eyev, normalv);
+                let c1 = shading::shade(&s.material, light, pnt, pnt, eyev, normalv, false);
                 this_canvas.plot(x_coord as i32, y_coord as i32, c1);
             }
         }
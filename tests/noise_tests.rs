@@ -0,0 +1,86 @@
+#[cfg(test)]
+
+use frog::primatives;
+use frog::noise;
+use frog::color;
+use frog::patterns::{Pattern};
+use frog::patterns::{stripe, ring, perturbed};
+use frog::matrix::transformations;
+
+//A spread of points that are not on the whole numbers
+fn sample_points() -> Vec<primatives::PointT> {
+    let mut ret_val = vec![];
+    for i in 0..1000 {
+        let f = i as f64;
+        ret_val.push(primatives::point(f * 0.731 - 300.0, f * 0.377 + 0.1, f * -0.913 + 0.2));
+    }
+
+    return ret_val
+}
+
+#[test]
+fn perlin_noise() {
+    //Zero on the lattice
+    assert_eq!(noise::perlin(primatives::point(0.0, 0.0, 0.0)), 0.0);
+    assert_eq!(noise::perlin(primatives::point(3.0, -7.0, 12.0)), 0.0);
+
+    let p = primatives::point(1.3, -2.7, 4.1);
+    assert_eq!(noise::perlin(p), noise::perlin(p));
+
+    let mut positive = 0;
+    for p in sample_points() {
+        let n = noise::perlin(p);
+        assert!((-1.0..=1.0).contains(&n));
+
+        //Smooth, so tiny steps give tiny changes
+        let nearby = noise::perlin(primatives::point(p.x + 0.001, p.y, p.z));
+        assert!((n - nearby).abs() < 0.01);
+
+        if n > 0.0 {
+            positive += 1;
+        }
+    }
+
+    //Centered on zero, and not a constant
+    assert!(positive > 300 && positive < 700);
+}
+
+#[test]
+fn fractal_noise_and_turbulence() {
+    let p = primatives::point(1.3, -2.7, 4.1);
+    assert_eq!(noise::fractal(p, 0, 0.5), 0.0);
+    assert_eq!(noise::fractal(p, 1, 0.5), noise::perlin(p));
+    assert!(noise::fractal(p, 4, 0.5) != noise::perlin(p));
+
+    assert_eq!(noise::turbulence(p, 0), 0.0);
+    assert_eq!(noise::turbulence(p, 1), noise::perlin(p).abs());
+
+    for p in sample_points() {
+        assert!((-1.0..=1.0).contains(&noise::fractal(p, 5, 0.5)));
+        assert!((0.0..=1.0).contains(&noise::turbulence(p, 5)));
+    }
+}
+
+#[test]
+fn perturbed_patterns() {
+    let white = color::new(1.0, 1.0, 1.0);
+    let black = color::BLACK;
+
+    //No jitter, no change
+    let still = perturbed::new(stripe::new(white, black), 0.0, 3);
+    for p in sample_points() {
+        assert_eq!(still.color_at(p), stripe::new(white, black).color_at(p));
+    }
+
+    //Marble. Most points keep their stripe, but the edges wander
+    let marble = transformations::new_scaling_matrix(0.5, 0.5, 0.5) * perturbed::new(stripe::new(white, black), 0.3, 3);
+    let plain = transformations::new_scaling_matrix(0.5, 0.5, 0.5) * stripe::new(white, black);
+    let changed = sample_points().iter().filter(|p| marble.color_at(**p) != plain.color_at(**p)).count();
+    assert!(changed > 0 && changed < 500);
+    assert_eq!(marble.color_at(primatives::point(0.7, 0.2, 0.4)), marble.color_at(primatives::point(0.7, 0.2, 0.4)));
+
+    //Wood grain
+    let wood = perturbed::new(ring::new(color::new(0.6, 0.4, 0.2), color::new(0.4, 0.25, 0.1)), 0.2, 2);
+    let changed = sample_points().iter().filter(|p| wood.color_at(**p) != ring::new(color::new(0.6, 0.4, 0.2), color::new(0.4, 0.25, 0.1)).color_at(**p)).count();
+    assert!(changed > 0);
+}
@@ -0,0 +1,251 @@
+#[cfg(test)]
+
+use frog::obj;
+use frog::primatives;
+use frog::world;
+use frog::camera;
+use frog::ray;
+use frog::shapes::Shape;
+
+#[test]
+fn ignores_unsupported_lines() {
+    let text = "There was a young lady named Bright\n\
+                who traveled much faster than light.\n\
+                \n\
+                mtllib scene.mtl\n\
+                v 1 0 0 # a comment after a vertex\n\
+                # a comment on its own\n";
+
+    let parsed = obj::parse(text).unwrap();
+    assert_eq!(parsed.vertices.len(), 1);
+    assert_eq!(parsed.ignored.len(), 3);
+    assert_eq!(parsed.ignored[0].line, 1);
+    assert_eq!(parsed.ignored[1].line, 2);
+    assert_eq!(parsed.ignored[2], obj::IgnoredLine { line: 4, text: "mtllib scene.mtl".to_string() });
+}
+
+#[test]
+fn vertex_records() {
+    let text = "v -1 1 0\n\
+                v -1.0000 0.5000 0.0000\n\
+                v 1 0 0\n\
+                v 1 1 0\n\
+                vn 0 0 2\n\
+                vn 0.707 0 -0.707\n\
+                vt 0.25 0.75\n\
+                vt 0.5\n";
+
+    let parsed = obj::parse(text).unwrap();
+    assert_eq!(parsed.vertices, vec![
+        primatives::point(-1.0, 1.0, 0.0),
+        primatives::point(-1.0, 0.5, 0.0),
+        primatives::point(1.0, 0.0, 0.0),
+        primatives::point(1.0, 1.0, 0.0)
+    ]);
+    assert_eq!(parsed.normals[0], primatives::vec3(0.0, 0.0, 1.0));
+    assert_eq!(parsed.normals[1], primatives::vec3(0.707, 0.0, -0.707).normalized());
+    assert_eq!(parsed.texture_coords, vec![(0.25, 0.75), (0.5, 0.0)]);
+}
+
+#[test]
+fn faces() {
+    let text = "v -1 1 0\n\
+                v -1 0 0\n\
+                v 1 0 0\n\
+                v 1 1 0\n\
+                f 1 2 3\n\
+                f 1 3 4\n";
+
+    let parsed = obj::parse(text).unwrap();
+    assert_eq!(parsed.groups.len(), 1);
+    let g = &parsed.groups[0];
+    assert_eq!(g.name, "");
+    assert_eq!(g.triangles.len(), 2);
+    assert_eq!(g.triangles[0].p1, parsed.vertices[0]);
+    assert_eq!(g.triangles[0].p2, parsed.vertices[1]);
+    assert_eq!(g.triangles[0].p3, parsed.vertices[2]);
+    assert_eq!(g.triangles[1].p1, parsed.vertices[0]);
+    assert_eq!(g.triangles[1].p2, parsed.vertices[2]);
+    assert_eq!(g.triangles[1].p3, parsed.vertices[3]);
+}
+
+#[test]
+fn polygon_fan_triangulation() {
+    let text = "v -1 1 0\n\
+                v -1 0 0\n\
+                v 1 0 0\n\
+                v 1 1 0\n\
+                v 0 2 0\n\
+                f 1 2 3 4 5\n";
+
+    let parsed = obj::parse(text).unwrap();
+    let g = &parsed.groups[0];
+    assert_eq!(g.triangles.len(), 3);
+    for (i, t) in g.triangles.iter().enumerate() {
+        assert_eq!(t.p1, parsed.vertices[0]);
+        assert_eq!(t.p2, parsed.vertices[i + 1]);
+        assert_eq!(t.p3, parsed.vertices[i + 2]);
+    }
+}
+
+#[test]
+fn named_groups() {
+    let text = "v -1 1 0\n\
+                v -1 0 0\n\
+                v 1 0 0\n\
+                v 1 1 0\n\
+                g FirstGroup\n\
+                f 1 2 3\n\
+                o Second Object\n\
+                f 1 3 4\n\
+                f -4 -2 -1\n";
+
+    let parsed = obj::parse(text).unwrap();
+    assert_eq!(parsed.groups.len(), 2);
+
+    let first = parsed.group("FirstGroup").unwrap();
+    assert_eq!(first.triangles.len(), 1);
+    assert_eq!(first.triangles[0].p3, parsed.vertices[2]);
+
+    //Negative indices count back from the last vertex
+    let second = parsed.group("Second Object").unwrap();
+    assert_eq!(second.triangles.len(), 2);
+    assert_eq!(second.triangles[1].p1, parsed.vertices[0]);
+    assert_eq!(second.triangles[1].p2, parsed.vertices[2]);
+    assert_eq!(second.triangles[1].p3, parsed.vertices[3]);
+
+    assert!(parsed.group("Missing").is_none());
+    assert_eq!(parsed.shapes().len(), 3);
+}
+
+#[test]
+fn repeated_group_names() {
+    let text = "v -1 1 0\n\
+                v -1 0 0\n\
+                v 1 0 0\n\
+                v 1 1 0\n\
+                g Wall\n\
+                f 1 2 3\n\
+                g Door\n\
+                f 1 3 4\n\
+                g Wall\n\
+                f 2 3 4\n";
+
+    let parsed = obj::parse(text).unwrap();
+    assert_eq!(parsed.groups.len(), 2);
+    assert_eq!(parsed.group("Wall").unwrap().triangles.len(), 2);
+    assert_eq!(parsed.group("Door").unwrap().triangles.len(), 1);
+    assert_eq!(parsed.group("Wall").unwrap().triangles[1].p1, parsed.vertices[1]);
+}
+
+#[test]
+fn faces_with_texture_coords() {
+    let text = "v 0 1 0\n\
+                v -1 0 0\n\
+                v 1 0 0\n\
+                vt 0.5 1\n\
+                vt 0 0\n\
+                vt 1 0\n\
+                vn 0 0 -1\n\
+                f 1/1 2/2 3/3\n\
+                f 1/1/1 2/2/1 3/3/1\n\
+                f 1/1 2 3/3\n";
+
+    let parsed = obj::parse(text).unwrap();
+    let g = &parsed.groups[0];
+    assert_eq!(g.triangles[0].texture_coords, Some([(0.5, 1.0), (0.0, 0.0), (1.0, 0.0)]));
+    assert_eq!(g.smooth_triangles[0].texture_coords, g.triangles[0].texture_coords);
+
+    //Corners without a vt leave the whole triangle without texture coordinates
+    assert_eq!(g.triangles[1].texture_coords, None);
+    assert_eq!(g.triangles[1].texture_coords_at(0.25, 0.25), None);
+
+    //u and v from an intersection blend between the corners
+    let r = ray::new(primatives::point(0.0, 0.5, -2.0), primatives::vec3(0.0, 0.0, 1.0));
+    let i = &g.triangles[0].intersect(r)[0];
+    let (tu, tv) = g.triangles[0].texture_coords_at(i.u, i.v).unwrap();
+    assert!((tu - 0.5).abs() < 1e-9);
+    assert!((tv - 0.5).abs() < 1e-9);
+    assert_eq!(g.smooth_triangles[0].texture_coords_at(0.0, 1.0), Some((1.0, 0.0)));
+}
+
+#[test]
+fn faces_with_normals() {
+    let text = "v 0 1 0\n\
+                v -1 0 0\n\
+                v 1 0 0\n\
+                vt 0 0\n\
+                vn -1 0 0\n\
+                vn 1 0 0\n\
+                vn 0 1 0\n\
+                f 1//3 2//1 3//2\n\
+                f 1/1/3 2/1/1 3/1/2\n\
+                f 1/1 2/1 3/1\n";
+
+    let parsed = obj::parse(text).unwrap();
+    let g = &parsed.groups[0];
+    assert_eq!(g.smooth_triangles.len(), 2);
+    assert_eq!(g.triangles.len(), 1);
+
+    let t = &g.smooth_triangles[0];
+    assert_eq!(t.p1, parsed.vertices[0]);
+    assert_eq!(t.n1, parsed.normals[2]);
+    assert_eq!(t.n2, parsed.normals[0]);
+    assert_eq!(t.n3, parsed.normals[1]);
+    assert_eq!(g.smooth_triangles[1].n1, t.n1);
+}
+
+#[test]
+fn parse_errors() {
+    let cases = [
+        ("v 1 2\n", 1),
+        ("v 1 2 x\n", 1),
+        ("v 1 2 3\nv 1 2 4\nf 1 2\n", 3),
+        ("v 1 2 3\nf 1 2 3\n", 2),
+        ("v 1 2 3\nv 1 2 4\nv 1 3 3\nf 0 1 2\n", 4),
+        ("v 1 2 3\nv 1 2 4\nv 1 3 3\n\nf 1//1 2 3\n", 5),
+        ("v 1 2 3\nv 1 2 4\nv 1 3 3\nf 1/2/3/4 2 3\n", 4),
+        ("v 1 2 3\nvn 0 0 0\n", 2),
+        ("vn 1 inf 0\n", 1),
+    ];
+
+    for (text, line) in cases {
+        match obj::parse(text) {
+            Err(obj::ObjError::Parse { line: l, message }) => {
+                assert_eq!(l, line);
+                assert!(!message.is_empty());
+            },
+            other => panic!("Expected an error on line {}, got {:?}", line, other)
+        }
+    }
+
+    let e = obj::parse("v 1 2 3\nf 1 2 3\n").unwrap_err();
+    assert_eq!(format!("{}", e), "OBJ line 2: Vertex index 2 is out of range, there are 1");
+
+    match obj::load("/this/file/does/not/exist.obj") {
+        Err(obj::ObjError::Io(_)) => (),
+        other => panic!("Expected an IO error, got {:?}", other)
+    }
+}
+
+#[test]
+fn load_into_world() {
+    let filename = std::env::temp_dir().join("frog_obj_test_square.obj");
+    std::fs::write(&filename, "v -1 -1 0\nv 1 -1 0\nv 1 1 0\nv -1 1 0\nf 1 2 3 4\n").unwrap();
+
+    let parsed = obj::load(filename.to_str().unwrap()).unwrap();
+    std::fs::remove_file(&filename).unwrap();
+
+    let c = camera::new(0, 0, 0.0);
+    let mut w = world::new(c);
+    w.objects.extend(parsed.shapes());
+    assert_eq!(w.objects.len(), 2);
+
+    let r = ray::new(primatives::point(0.5, -0.25, -5.0), primatives::vec3(0.0, 0.0, 1.0));
+    let intersections = w.intersect(r);
+    assert_eq!(intersections.len(), 1);
+    assert_eq!(intersections[0].time, 5.0);
+
+    let r = ray::new(primatives::point(1.5, 0.5, -5.0), primatives::vec3(0.0, 0.0, 1.0));
+    assert_eq!(w.intersect(r).len(), 0);
+}
@@ -0,0 +1,222 @@
+#[cfg(test)]
+
+use std::sync::Arc;
+
+use frog::primatives;
+use frog::color;
+use frog::material;
+use frog::light;
+use frog::bump;
+use frog::shading;
+use frog::ray;
+use frog::patterns;
+use frog::patterns::{Pattern};
+use frog::patterns::{stripe, gradient, ring, checker, blend};
+use frog::shapes::sphere;
+use frog::shapes::plane;
+use frog::shapes::group;
+use frog::shapes::intersection;
+use frog::shapes::{Shape};
+use frog::matrix;
+use frog::matrix::transformations;
+
+fn white() -> color::Color {
+    return color::new(1.0, 1.0, 1.0);
+}
+
+fn black() -> color::Color {
+    return color::BLACK;
+}
+
+#[test]
+fn stripe_pattern() {
+    let p = stripe::new(white(), black());
+    assert_eq!(p.transformation, matrix::IDENTITY_MATRIX_4X4);
+
+    //Constant in y and z
+    assert_eq!(p.color_at(primatives::point(0.0, 0.0, 0.0)), white());
+    assert_eq!(p.color_at(primatives::point(0.0, 1.0, 0.0)), white());
+    assert_eq!(p.color_at(primatives::point(0.0, 2.0, 0.0)), white());
+    assert_eq!(p.color_at(primatives::point(0.0, 0.0, 1.0)), white());
+    assert_eq!(p.color_at(primatives::point(0.0, 0.0, 2.0)), white());
+
+    //Alternates in x, including below zero
+    assert_eq!(p.color_at(primatives::point(0.9, 0.0, 0.0)), white());
+    assert_eq!(p.color_at(primatives::point(1.0, 0.0, 0.0)), black());
+    assert_eq!(p.color_at(primatives::point(-0.1, 0.0, 0.0)), black());
+    assert_eq!(p.color_at(primatives::point(-1.0, 0.0, 0.0)), black());
+    assert_eq!(p.color_at(primatives::point(-1.1, 0.0, 0.0)), white());
+}
+
+#[test]
+fn gradient_pattern() {
+    let p = gradient::new(white(), black());
+    assert_eq!(p.color_at(primatives::point(0.0, 0.0, 0.0)), white());
+    assert_eq!(p.color_at(primatives::point(0.25, 0.0, 0.0)), color::new(0.75, 0.75, 0.75));
+    assert_eq!(p.color_at(primatives::point(0.5, 0.0, 0.0)), color::new(0.5, 0.5, 0.5));
+    assert_eq!(p.color_at(primatives::point(0.75, 0.0, 0.0)), color::new(0.25, 0.25, 0.25));
+    assert_eq!(p.color_at(primatives::point(-0.25, 0.0, 0.0)), color::new(0.25, 0.25, 0.25));
+}
+
+#[test]
+fn ring_pattern() {
+    let p = ring::new(white(), black());
+    assert_eq!(p.color_at(primatives::point(0.0, 0.0, 0.0)), white());
+    assert_eq!(p.color_at(primatives::point(1.0, 0.0, 0.0)), black());
+    assert_eq!(p.color_at(primatives::point(0.0, 0.0, 1.0)), black());
+    assert_eq!(p.color_at(primatives::point(0.708, 0.0, 0.708)), black());
+    assert_eq!(p.color_at(primatives::point(0.0, 5.0, 2.5)), white());
+}
+
+#[test]
+fn checker_pattern() {
+    let p = checker::new(white(), black());
+
+    //Repeats in x, y and z
+    assert_eq!(p.color_at(primatives::point(0.0, 0.0, 0.0)), white());
+    assert_eq!(p.color_at(primatives::point(0.99, 0.0, 0.0)), white());
+    assert_eq!(p.color_at(primatives::point(1.01, 0.0, 0.0)), black());
+    assert_eq!(p.color_at(primatives::point(0.0, 0.99, 0.0)), white());
+    assert_eq!(p.color_at(primatives::point(0.0, 1.01, 0.0)), black());
+    assert_eq!(p.color_at(primatives::point(0.0, 0.0, 0.99)), white());
+    assert_eq!(p.color_at(primatives::point(0.0, 0.0, 1.01)), black());
+    assert_eq!(p.color_at(primatives::point(1.5, 0.0, 1.5)), white());
+    assert_eq!(p.color_at(primatives::point(-0.5, 0.0, 0.5)), black());
+}
+
+#[test]
+fn pattern_transformations() {
+    let mut s = transformations::new_scaling_matrix(2.0, 2.0, 2.0) * sphere::new(1.0, primatives::point(0.0, 0.0, 0.0));
+    s.material.pattern = Some(Arc::new(stripe::new(white(), black())));
+
+    //Object transformation
+    let object_point = s.world_to_object(primatives::point(1.5, 0.0, 0.0));
+    assert_eq!(s.material.color_at(object_point), white());
+
+    //Pattern transformation
+    let p = transformations::new_scaling_matrix(2.0, 2.0, 2.0) * stripe::new(white(), black());
+    assert_eq!(p.color_at(primatives::point(1.5, 0.0, 0.0)), white());
+
+    //Both
+    let p = transformations::new_translation_matrix(0.5, 0.0, 0.0) * stripe::new(white(), black());
+    s.material.pattern = Some(Arc::new(p));
+    let object_point = s.world_to_object(primatives::point(2.5, 0.0, 0.0));
+    assert_eq!(s.material.color_at(object_point), white());
+
+    //Without a pattern, the material's color is used
+    let m = material::new_default();
+    assert_eq!(m.color_at(primatives::point(5.0, 0.0, 0.0)), m.color);
+}
+
+#[test]
+fn nested_and_blended_patterns() {
+    let red = color::new(1.0, 0.0, 0.0);
+    let green = color::new(0.0, 1.0, 0.0);
+    let blue = color::new(0.0, 0.0, 1.0);
+
+    //Checkers made of stripes, where the stripes are a quarter as wide
+    let thin = transformations::new_scaling_matrix(0.25, 0.25, 0.25) * stripe::new(red, green);
+    let p = checker::new(thin, blue);
+    assert_eq!(p.color_at(primatives::point(0.1, 0.0, 0.0)), red);
+    assert_eq!(p.color_at(primatives::point(0.3, 0.0, 0.0)), green);
+    assert_eq!(p.color_at(primatives::point(1.1, 0.0, 0.0)), blue);
+
+    //Sub-patterns see points in the space of the pattern they are in
+    let outer = transformations::new_scaling_matrix(2.0, 2.0, 2.0) * stripe::new(stripe::new(red, green), blue);
+    assert_eq!(outer.color_at(primatives::point(0.5, 0.0, 0.0)), red);
+    assert_eq!(outer.color_at(primatives::point(1.5, 0.0, 0.0)), red);
+    assert_eq!(outer.color_at(primatives::point(2.5, 0.0, 0.0)), blue);
+
+    //Plaid, from two crossed stripes
+    let across = stripe::new(red, white());
+    let down = transformations::new_rotation_y_matrix(transformations::PI / 2.0) * stripe::new(blue, white());
+    let p = blend::new(across, down);
+    assert_eq!(p.color_at(primatives::point(0.5, 0.0, -0.5)), color::new(0.5, 0.0, 0.5));
+    assert_eq!(p.color_at(primatives::point(0.5, 0.0, 0.5)), color::new(1.0, 0.5, 0.5));
+    assert_eq!(p.color_at(primatives::point(1.5, 0.0, -0.5)), color::new(0.5, 0.5, 1.0));
+    assert_eq!(p.color_at(primatives::point(1.5, 0.0, 0.5)), white());
+
+    //Any pattern can stand in for another
+    let patterns: Vec<Arc<dyn Pattern>> = vec![
+        Arc::new(gradient::new(red, red)),
+        Arc::new(ring::new(red, red)),
+        Arc::new(patterns::solid(red)),
+        red.into()
+    ];
+
+    for p in patterns {
+        assert_eq!(p.color_at(primatives::point(3.7, -1.2, 0.4)), red);
+    }
+}
+
+#[test]
+fn shading_with_pattern() {
+    let mut m = material::new(white(), 1.0, 0.0, 0.0, 200.0);
+    m.pattern = Some(Arc::new(stripe::new(white(), black())));
+
+    let eyev = primatives::vec3(0.0, 0.0, -1.0);
+    let normalv = primatives::vec3(0.0, 0.0, -1.0);
+    let l = light::new(white(), primatives::point(0.0, 0.0, -10.0));
+
+    let p1 = primatives::point(0.9, 0.0, 0.0);
+    let p2 = primatives::point(1.1, 0.0, 0.0);
+    assert_eq!(shading::shade(&m, l, p1, p1, eyev, normalv, false), white());
+    assert_eq!(shading::shade(&m, l, p2, p2, eyev, normalv, false), black());
+
+    //The pattern is looked up where the point is on the object, not in the world
+    let mut floor = transformations::new_translation_matrix(0.5, 0.0, 0.0) * plane::new();
+    floor.material = m.clone();
+    let r = ray::new(primatives::point(0.25, 1.0, 0.0), primatives::vec3(0.0, -1.0, 0.0));
+    let i = floor.intersect(r)[0];
+    assert_eq!(i.object_point.x, -0.25);
+    assert_eq!(shading::shade_intersection(i, l, false), black());
+
+    //Even for shapes inside of a group
+    let mut ball = sphere::new(1.0, primatives::point(0.0, 0.0, 0.0));
+    ball.material = m;
+    let mut g = group::new();
+    g.add_child(&ball);
+    let g = transformations::new_translation_matrix(5.0, 0.0, 0.0) * g;
+
+    let r = ray::new(primatives::point(5.0, 0.0, -5.0), primatives::vec3(0.0, 0.0, 1.0));
+    let i = intersection::hit(&g.intersect(r)).unwrap();
+    assert_eq!(i.location, primatives::point(5.0, 0.0, -1.0));
+    assert_eq!(i.object_point, primatives::point(0.0, 0.0, -1.0001));
+    assert_eq!(shading::shade_intersection(i, l, false), white());
+}
+
+#[test]
+fn material_equality_with_patterns() {
+    //Materials with patterns are only equal if they share the same pattern
+    let p: Arc<dyn Pattern> = Arc::new(stripe::new(white(), black()));
+    let mut m1 = material::new_default();
+    m1.pattern = Some(p.clone());
+    let mut m2 = material::new_default();
+    m2.pattern = Some(p);
+    assert_eq!(m1, m2);
+    assert_eq!(m1.clone(), m1);
+
+    let mut m3 = material::new_default();
+    m3.pattern = Some(Arc::new(stripe::new(white(), black())));
+    assert!(m1 != m3);
+    assert!(m1 != material::new_default());
+
+    //The same goes for bumps
+    let mut bumpy = material::new_default();
+    bumpy.bump = Some(bump::new_noise(4.0, 0.3, 2));
+    assert!(bumpy != material::new_default());
+    assert_eq!(bumpy.clone(), bumpy);
+
+    let mut deeper = bumpy.clone();
+    deeper.bump = Some(bump::new_noise(4.0, 0.6, 2));
+    assert!(bumpy != deeper);
+
+    let height: Arc<dyn Pattern> = Arc::new(gradient::new(black(), white()));
+    let mut m4 = material::new_default();
+    m4.bump = Some(bump::new_height_map(height.clone(), 0.5));
+    let mut m5 = material::new_default();
+    m5.bump = Some(bump::new_height_map(height, 0.5));
+    assert_eq!(m4, m5);
+    m5.bump = Some(bump::new_height_map(gradient::new(black(), white()), 0.5));
+    assert!(m4 != m5);
+}
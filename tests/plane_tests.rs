@@ -0,0 +1,105 @@
+#[cfg(test)]
+
+use frog::primatives;
+use frog::shapes::plane;
+use frog::shapes::sphere;
+use frog::shapes::{Shape, Surface};
+use frog::ray;
+use frog::matrix;
+use frog::matrix::transformations;
+use frog::material;
+use frog::world;
+use frog::camera;
+use frog::color;
+
+#[test]
+fn plane_creation() {
+    let p = plane::new();
+    assert_eq!(p.transformation, matrix::IDENTITY_MATRIX_4X4);
+    assert_eq!(p.material, material::new_default());
+
+    let t = transformations::new_translation_matrix(0.0, -1.0, 0.0);
+    let p = t * p;
+    assert_eq!(p.transformation, t);
+}
+
+#[test]
+fn plane_normal() {
+    let p = plane::new();
+
+    let n = p.normal_at(primatives::point(0.0, 0.0, 0.0));
+    n.check_type(primatives::TYPE_VEC);
+    assert_eq!(n, primatives::vec3(0.0, 1.0, 0.0));
+
+    let n = p.normal_at(primatives::point(10.0, 0.0, -10.0));
+    assert_eq!(n, primatives::vec3(0.0, 1.0, 0.0));
+
+    let n = p.normal_at(primatives::point(-5.0, 0.0, 150.0));
+    assert_eq!(n, primatives::vec3(0.0, 1.0, 0.0));
+
+    //A wall, rotated up to face down the z axis
+    let p = transformations::new_rotation_x_matrix(transformations::PI / 2.0) * plane::new();
+    let n = p.normal_at(primatives::point(1.0, 1.0, 0.0));
+    assert_eq!(n, primatives::vec3(0.0, 0.0, 1.0));
+}
+
+#[test]
+fn ray_plane_intersection() {
+    let p = plane::new();
+
+    //Ray parallel to the plane
+    let r = ray::new(primatives::point(0.0, 10.0, 0.0), primatives::vec3(0.0, 0.0, 1.0));
+    assert_eq!(p.intersect(r).len(), 0);
+
+    //Coplanar ray
+    let r = ray::new(primatives::point(0.0, 0.0, 0.0), primatives::vec3(0.0, 0.0, 1.0));
+    assert_eq!(p.intersect(r).len(), 0);
+
+    //Ray from above
+    let r = ray::new(primatives::point(0.0, 1.0, 0.0), primatives::vec3(0.0, -1.0, 0.0));
+    let intersections = p.intersect(r);
+    assert_eq!(intersections.len(), 1);
+    assert_eq!(intersections[0].time, 1.0);
+    assert_eq!(intersections[0].location, primatives::point(0.0, 0.0, 0.0));
+    assert_eq!(intersections[0].normalv, primatives::vec3(0.0, 1.0, 0.0));
+
+    //Ray from below sees the underside of the plane
+    let r = ray::new(primatives::point(0.0, -1.0, 0.0), primatives::vec3(0.0, 1.0, 0.0));
+    let intersections = p.intersect(r);
+    assert_eq!(intersections.len(), 1);
+    assert_eq!(intersections[0].time, 1.0);
+    assert_eq!(intersections[0].normalv, primatives::vec3(0.0, -1.0, 0.0));
+    assert_eq!(intersections[0].inside, true);
+
+    //Transformed plane
+    let p = transformations::new_translation_matrix(0.0, -1.0, 0.0) * plane::new();
+    let r = ray::new(primatives::point(0.0, 1.0, 0.0), primatives::vec3(0.0, -1.0, 0.0));
+    let intersections = p.intersect(r);
+    assert_eq!(intersections.len(), 1);
+    assert_eq!(intersections[0].time, 2.0);
+    assert_eq!(intersections[0].location, primatives::point(0.0, -1.0, 0.0));
+}
+
+#[test]
+fn plane_in_world() {
+    let c = camera::new(0, 0, 0.0);
+    let mut w = world::new(c);
+
+    let floor = transformations::new_translation_matrix(0.0, -1.0, 0.0) * plane::new();
+    let s = sphere::new(1.0, primatives::point(0.0, 0.0, 0.0));
+    w.objects.push(&floor);
+    w.objects.push(&s);
+
+    let r = ray::new(primatives::point(0.0, 5.0, -5.0), primatives::vec3(0.0, -1.0, 0.0));
+    let intersections = w.intersect(r);
+    assert_eq!(intersections.len(), 1);
+    assert_eq!(intersections[0].time, 6.0);
+
+    //The floor beneath the sphere is in its shadow
+    w.lights[0].location = primatives::point(0.0, 10.0, 0.0);
+    let r = ray::new(primatives::point(0.0, -0.5, 0.0), primatives::vec3(0.0, -1.0, 0.0));
+    assert_eq!(w.color_at_ray(r), color::new(0.1, 0.1, 0.1));
+
+    let r = ray::new(primatives::point(3.0, -0.5, 0.0), primatives::vec3(0.0, -1.0, 0.0));
+    assert!(w.color_at_ray(r).red > 0.1);
+}
@@ -0,0 +1,156 @@
+#[cfg(test)]
+
+use frog::zlib;
+use frog::png;
+
+/* A minimal inflate that only understands what zlib::deflate writes (one
+ * fixed Huffman block), so compressed data can be checked by round trip
+ */
+fn inflate_fixed(data: &[u8]) -> Vec<u8> {
+    let mut bit = 0;
+    let mut read_bits = |n: u32| -> u32 {
+        let mut v = 0;
+        for i in 0..n {
+            let b = (data[bit / 8] >> (bit % 8)) & 1;
+            v |= (b as u32) << i;
+            bit += 1;
+        }
+        return v
+    };
+
+    assert_eq!(read_bits(1), 1);
+    assert_eq!(read_bits(2), 1);
+
+    let lengths = [3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258];
+    let length_extra = [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0];
+    let distances = [1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577];
+
+    let mut out: Vec<u8> = vec![];
+    loop {
+        //Read a code highest bit first, until it is one of the fixed codes
+        let mut code = 0;
+        let mut len = 0;
+        let symbol = loop {
+            code = (code << 1) | read_bits(1);
+            len += 1;
+            match (len, code) {
+                (7, 0..=23) => break code + 256,
+                (8, 48..=191) => break code - 48,
+                (8, 192..=199) => break code - 192 + 280,
+                (9, 400..=511) => break code - 400 + 144,
+                _ => assert!(len < 9)
+            }
+        };
+
+        if symbol < 256 {
+            out.push(symbol as u8);
+        } else if symbol == 256 {
+            return out
+        } else {
+            let i = symbol as usize - 257;
+            let length = lengths[i] + read_bits(length_extra[i]) as usize;
+
+            let mut d = 0;
+            for _ in 0..5 {
+                d = (d << 1) | read_bits(1);
+            }
+            let extra = if d < 4 { 0 } else { (d / 2) - 1 };
+            let distance = distances[d as usize] + read_bits(extra) as usize;
+
+            for _ in 0..length {
+                out.push(out[out.len() - distance]);
+            }
+        }
+    }
+}
+
+#[test]
+fn checksums() {
+    assert_eq!(png::crc32(b""), 0);
+    assert_eq!(png::crc32(b"123456789"), 0xCBF43926);
+    assert_eq!(png::crc32(b"IEND"), 0xAE426082);
+
+    assert_eq!(zlib::adler32(b""), 1);
+    assert_eq!(zlib::adler32(b"Wikipedia"), 0x11E60398);
+
+    //Long enough for the sums to wrap
+    let zeros_and_ones = vec![255; 100000];
+    let (mut a, mut b) = (1u64, 0u64);
+    for byte in &zeros_and_ones {
+        a = (a + *byte as u64) % 65521;
+        b = (b + a) % 65521;
+    }
+    assert_eq!(zlib::adler32(&zeros_and_ones), ((b << 16) | a) as u32);
+}
+
+#[test]
+fn deflate_round_trip() {
+    let cases: Vec<Vec<u8>> = vec![
+        vec![],
+        b"a".to_vec(),
+        b"abcabcabcabcabcabcabcabc".to_vec(),
+        vec![7; 1000],
+        (0..70000u32).map(|i| ((i * 7919) % 251) as u8 ^ ((i / 1000) as u8)).collect(),
+        (0..=255).collect()
+    ];
+
+    for data in cases {
+        assert_eq!(inflate_fixed(&zlib::deflate(&data)), data);
+    }
+
+    //Runs shrink a lot
+    assert!(zlib::deflate(&vec![7; 100000]).len() < 1000);
+}
+
+#[test]
+fn zlib_stream() {
+    let data = b"hello hello hello hello".to_vec();
+    let z = zlib::compress(&data);
+
+    assert_eq!(z[0], 0x78);
+    assert_eq!(((z[0] as u32) * 256 + z[1] as u32) % 31, 0);
+    assert_eq!(z[(z.len() - 4)..], zlib::adler32(&data).to_be_bytes());
+    assert_eq!(inflate_fixed(&z[2..(z.len() - 4)]), data);
+}
+
+#[test]
+fn png_encoding() {
+    //2x2: red, green / blue, white
+    let rgb = [255, 0, 0, 0, 255, 0, 0, 0, 255, 255, 255, 255];
+    let file = png::encode(2, 2, &rgb);
+
+    assert_eq!(file[0..8], [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n']);
+
+    //Walk the chunks, checking lengths and CRCs
+    let mut chunks = vec![];
+    let mut i = 8;
+    while i < file.len() {
+        let length = u32::from_be_bytes(file[i..(i + 4)].try_into().unwrap()) as usize;
+        let kind = &file[(i + 4)..(i + 8)];
+        let data = &file[(i + 8)..(i + 8 + length)];
+        let crc = u32::from_be_bytes(file[(i + 8 + length)..(i + 12 + length)].try_into().unwrap());
+        assert_eq!(png::crc32(&file[(i + 4)..(i + 8 + length)]), crc);
+
+        chunks.push((kind.to_vec(), data.to_vec()));
+        i += 12 + length;
+    }
+
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks[0].0, b"IHDR");
+    assert_eq!(chunks[0].1, [0, 0, 0, 2, 0, 0, 0, 2, 8, 2, 0, 0, 0]);
+    assert_eq!(chunks[1].0, b"IDAT");
+    assert_eq!(chunks[2].0, b"IEND");
+    assert_eq!(chunks[2].1.len(), 0);
+
+    //Each row is a filter type followed by the filtered pixels
+    let idat = &chunks[1].1;
+    let filtered = inflate_fixed(&idat[2..(idat.len() - 4)]);
+    assert_eq!(filtered.len(), 2 * (1 + 2 * 3));
+    assert!(filtered[0] <= 4 && filtered[7] <= 4);
+}
+
+#[test]
+#[should_panic]
+fn png_wrong_size() {
+    png::encode(2, 2, &[0, 0, 0]);
+}
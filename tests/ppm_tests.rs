@@ -0,0 +1,95 @@
+#[cfg(test)]
+
+use frog::canvas;
+use frog::color;
+use frog::ppm;
+
+#[test]
+fn round_trip() {
+    let mut c = canvas::new(3, 2);
+    c.contents[0] = color::new(1.0, 0.0, 0.0);
+    c.contents[4] = color::new(0.0, 0.5, 1.0);
+    c.contents[5] = color::new(1.5, -0.5, 1.0);
+
+    for format in [canvas::Format::P3, canvas::Format::P6] {
+        let mut bytes = vec![];
+        c.write_to(&mut bytes, format).unwrap();
+        let read_back = ppm::parse(&bytes).unwrap();
+
+        assert_eq!(read_back.width, 3);
+        assert_eq!(read_back.height, 2);
+        assert_eq!(read_back.contents[0], color::new(1.0, 0.0, 0.0));
+        assert_eq!(read_back.contents[1], color::BLACK);
+
+        //Written values are rounded down to whole steps of 1/255, and clamped
+        assert_eq!(read_back.contents[4], color::new(0.0, 127.0 / 255.0, 1.0));
+        assert_eq!(read_back.contents[5], color::new(1.0, 0.0, 1.0));
+    }
+
+    let filename = std::env::temp_dir().join("frog_ppm_tests_round_trip.ppm");
+    let filename = filename.to_str().unwrap();
+    c.write_to_ppm(filename);
+    let read_back = ppm::load(filename).unwrap();
+    std::fs::remove_file(filename).unwrap();
+    assert_eq!(read_back.contents[0], color::new(1.0, 0.0, 0.0));
+}
+
+#[test]
+fn header_quirks() {
+    //Comments, tabs, CRLF, no line break before the pixels, a comment stuck to a number
+    let text = "P3 # plain\r\n# made by hand\r\n2\t1 # size\n10#max\n10 5 0   0\n0\n10";
+    let c = ppm::parse(text.as_bytes()).unwrap();
+    assert_eq!(c.width, 2);
+    assert_eq!(c.height, 1);
+    assert_eq!(c.contents[0], color::new(1.0, 0.5, 0.0));
+    assert_eq!(c.contents[1], color::new(0.0, 0.0, 1.0));
+
+    //Comments in a binary header, and pixel bytes that look like whitespace or #
+    let mut bytes = b"P6\n# comment\n1 2\n# another\n255\n".to_vec();
+    bytes.extend([b'#', b'\n', b' ', 255, 0, 51]);
+    let c = ppm::parse(&bytes).unwrap();
+    assert_eq!(c.contents[0], color::new(35.0 / 255.0, 10.0 / 255.0, 32.0 / 255.0));
+    assert_eq!(c.contents[1], color::new(1.0, 0.0, 0.2));
+}
+
+#[test]
+fn large_maximum_values() {
+    let c = ppm::parse(b"P3 1 1 65535 65535 0 32768").unwrap();
+    assert_eq!(c.contents[0], color::new(1.0, 0.0, 32768.0 / 65535.0));
+
+    //Two bytes per number, highest first
+    let mut bytes = b"P6 1 1 1000\n".to_vec();
+    bytes.extend([0x03, 0xE8, 0x01, 0xF4, 0x00, 0x00]);
+    let c = ppm::parse(&bytes).unwrap();
+    assert_eq!(c.contents[0], color::new(1.0, 0.5, 0.0));
+}
+
+#[test]
+fn errors() {
+    let error = |bytes: &[u8]| -> String {
+        return ppm::parse(bytes).unwrap_err().to_string();
+    };
+
+    assert_eq!(error(b""), "PPM byte 0: File ended early, expected the P3 or P6 magic number");
+    assert_eq!(error(b"P5 1 1 255 0"), "PPM byte 0: Unsupported format 'P5', expected P3 or P6");
+    assert_eq!(error(b"P3 x 1 255"), "PPM byte 2: Invalid width 'x'");
+    assert_eq!(error(b"P3 0 1 255"), "PPM byte 6: Image is empty (0 x 1)");
+    assert_eq!(error(b"P3 1 1 70000 0 0 0"), "PPM byte 6: Maximum value must be between 1 and 65535, found 70000");
+    assert_eq!(error(b"P3 1 1 0 0 0 0"), "PPM byte 6: Maximum value must be between 1 and 65535, found 0");
+    assert_eq!(error(b"P3 1 1 255 0 256 0"), "PPM byte 12: Color value 256 is over the maximum of 255");
+    assert_eq!(error(b"P3 1 1 255 0 0"), "PPM byte 14: File ended early, expected more pixels");
+    assert_eq!(error(b"P3 1 1 255 0 0 "), "PPM byte 15: File ended early, expected color value");
+    assert_eq!(error(b"P3 1 1 255 0 -1 0"), "PPM byte 12: Invalid color value '-1'");
+    assert_eq!(error(b"P6 1 1 255"), "PPM byte 10: Expected whitespace before pixel data");
+    assert_eq!(error(b"P6 1 1 255\n\x01\x02"), "PPM byte 13: File ended early, expected more pixels");
+
+    //Huge headers are caught before the canvas is made, rather than running out of memory
+    assert_eq!(error(b"P6 200000 200000 255\n\0\0\0"), "PPM byte 24: File ended early, expected more pixels");
+    assert_eq!(error(b"P3 200000 200000 255 0 0 0"), "PPM byte 26: File ended early, expected more pixels");
+    assert_eq!(error(b"P6 4294967295 4294967295 65535\n\0\0"), "PPM byte 24: Image is too large (4294967295 x 4294967295)");
+
+    match ppm::load("this/file/does/not/exist.ppm") {
+        Err(ppm::PpmError::Io(message)) => assert!(message.starts_with("this/file/does/not/exist.ppm")),
+        _ => panic!("Expected an IO error")
+    }
+}
@@ -0,0 +1,34 @@
+#[cfg(test)]
+
+use frog::random;
+
+#[test]
+fn jitter_range() {
+    for i in 0..1000 {
+        let j = random::jitter(&[i, 42]);
+        assert!((0.0..1.0).contains(&j));
+    }
+}
+
+#[test]
+fn jitter_is_repeatable() {
+    assert_eq!(random::jitter(&[1, 2, 3]), random::jitter(&[1, 2, 3]));
+    assert!(random::jitter(&[1, 2, 3]) != random::jitter(&[1, 2, 4]));
+    assert!(random::jitter(&[1, 2, 3]) != random::jitter(&[3, 2, 1]));
+
+    assert_eq!(random::hash(7), random::hash(7));
+    assert!(random::hash(7) != random::hash(8));
+}
+
+#[test]
+fn jitter_is_spread_out() {
+    //Roughly half of the values should land on either side of 0.5
+    let mut low = 0;
+    for i in 0..10000 {
+        if random::jitter(&[i]) < 0.5 {
+            low += 1;
+        }
+    }
+
+    assert!(low > 4800 && low < 5200);
+}
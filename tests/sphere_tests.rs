@@ -2,7 +2,7 @@
 
 use frog::primatives;
 use frog::shapes;
-use frog::shapes::{Shape};
+use frog::shapes::{Shape, Surface};
 use frog::ray;
 use frog::matrix::transformations;
 use frog::canvas;
@@ -0,0 +1,233 @@
+#[cfg(test)]
+
+use std::sync::Arc;
+
+use frog::primatives;
+use frog::color;
+use frog::canvas;
+use frog::ppm;
+use frog::patterns::{Pattern};
+use frog::patterns::uv;
+use frog::patterns::uv::{UvPattern, Mapping, CubeFace};
+use frog::patterns::image;
+use frog::shapes::sphere;
+use frog::shapes::{Shape};
+use frog::shapes::intersection;
+use frog::ray;
+use frog::matrix::transformations;
+
+fn white() -> color::Color {
+    return color::new(1.0, 1.0, 1.0);
+}
+
+fn assert_uv(uv: (f64, f64), expected: (f64, f64)) {
+    assert!((uv.0 - expected.0).abs() < 0.0001, "u was {}, expected {}", uv.0, expected.0);
+    assert!((uv.1 - expected.1).abs() < 0.0001, "v was {}, expected {}", uv.1, expected.1);
+}
+
+#[test]
+fn uv_checker() {
+    let c = uv::new_checker(2.0, 2.0, color::BLACK, white());
+    assert_eq!(c.uv_color_at(0.0, 0.0), color::BLACK);
+    assert_eq!(c.uv_color_at(0.5, 0.0), white());
+    assert_eq!(c.uv_color_at(0.0, 0.5), white());
+    assert_eq!(c.uv_color_at(0.5, 0.5), color::BLACK);
+    assert_eq!(c.uv_color_at(1.0, 1.0), color::BLACK);
+}
+
+#[test]
+fn spherical_mapping() {
+    let half_sqrt_2 = 2.0_f64.sqrt() / 2.0;
+    let cases = [
+        (primatives::point(0.0, 0.0, -1.0), (0.0, 0.5)),
+        (primatives::point(1.0, 0.0, 0.0), (0.25, 0.5)),
+        (primatives::point(0.0, 0.0, 1.0), (0.5, 0.5)),
+        (primatives::point(-1.0, 0.0, 0.0), (0.75, 0.5)),
+        (primatives::point(0.0, 1.0, 0.0), (0.5, 1.0)),
+        (primatives::point(0.0, -1.0, 0.0), (0.5, 0.0)),
+        (primatives::point(half_sqrt_2, half_sqrt_2, 0.0), (0.25, 0.75)),
+    ];
+
+    for (p, expected) in cases {
+        assert_uv(uv::spherical_map(p), expected);
+        assert_uv(Mapping::Spherical.uv_at(p), expected);
+    }
+}
+
+#[test]
+fn planar_mapping() {
+    let cases = [
+        (primatives::point(0.25, 0.0, 0.5), (0.25, 0.5)),
+        (primatives::point(0.25, 0.0, -0.25), (0.25, 0.75)),
+        (primatives::point(0.25, 0.5, -0.25), (0.25, 0.75)),
+        (primatives::point(1.25, 0.0, 0.5), (0.25, 0.5)),
+        (primatives::point(0.25, 0.0, -1.75), (0.25, 0.25)),
+        (primatives::point(1.0, 0.0, -1.0), (0.0, 0.0)),
+        (primatives::point(0.0, 0.0, 0.0), (0.0, 0.0)),
+    ];
+
+    for (p, expected) in cases {
+        assert_uv(Mapping::Planar.uv_at(p), expected);
+    }
+}
+
+#[test]
+fn cylindrical_mapping() {
+    let cases = [
+        (primatives::point(0.0, 0.0, -1.0), (0.0, 0.0)),
+        (primatives::point(0.0, 0.5, -1.0), (0.0, 0.5)),
+        (primatives::point(0.0, 1.0, -1.0), (0.0, 0.0)),
+        (primatives::point(0.70711, 0.5, -0.70711), (0.125, 0.5)),
+        (primatives::point(1.0, 0.5, 0.0), (0.25, 0.5)),
+        (primatives::point(0.70711, 0.5, 0.70711), (0.375, 0.5)),
+        (primatives::point(0.0, -0.25, 1.0), (0.5, 0.75)),
+        (primatives::point(-0.70711, 0.5, 0.70711), (0.625, 0.5)),
+        (primatives::point(-1.0, 1.25, 0.0), (0.75, 0.25)),
+        (primatives::point(-0.70711, 0.5, -0.70711), (0.875, 0.5)),
+    ];
+
+    for (p, expected) in cases {
+        assert_uv(Mapping::Cylindrical.uv_at(p), expected);
+    }
+}
+
+#[test]
+fn cube_mapping() {
+    let faces = [
+        (primatives::point(-1.0, 0.5, -0.25), CubeFace::Left),
+        (primatives::point(1.1, -0.75, 0.8), CubeFace::Right),
+        (primatives::point(0.1, 0.6, 0.9), CubeFace::Front),
+        (primatives::point(-0.7, 0.0, -2.0), CubeFace::Back),
+        (primatives::point(0.5, 1.0, 0.9), CubeFace::Up),
+        (primatives::point(-0.2, -1.3, 1.1), CubeFace::Down),
+    ];
+
+    for (p, expected) in faces {
+        assert_eq!(uv::cube_face(p), expected);
+    }
+
+    //Two points on each face, one in the upper left and one in the lower right
+    let cases = [
+        (primatives::point(-0.5, 0.5, 1.0), primatives::point(0.5, -0.5, 1.0)),
+        (primatives::point(0.5, 0.5, -1.0), primatives::point(-0.5, -0.5, -1.0)),
+        (primatives::point(-1.0, 0.5, -0.5), primatives::point(-1.0, -0.5, 0.5)),
+        (primatives::point(1.0, 0.5, 0.5), primatives::point(1.0, -0.5, -0.5)),
+        (primatives::point(-0.5, 1.0, -0.5), primatives::point(0.5, 1.0, 0.5)),
+        (primatives::point(-0.5, -1.0, 0.5), primatives::point(0.5, -1.0, -0.5)),
+    ];
+
+    for (upper_left, lower_right) in cases {
+        assert_uv(Mapping::Cube.uv_at(upper_left), (0.25, 0.75));
+        assert_uv(Mapping::Cube.uv_at(lower_right), (0.75, 0.25));
+    }
+
+    //A different color on every face
+    let colors = [
+        color::new(1.0, 0.0, 0.0),
+        color::new(1.0, 1.0, 0.0),
+        color::new(0.0, 1.0, 0.0),
+        color::new(0.0, 1.0, 1.0),
+        color::new(0.0, 0.0, 1.0),
+        color::new(1.0, 0.0, 1.0),
+    ];
+    let faces: Vec<Arc<dyn UvPattern>> = colors.iter().map(|c| Arc::new(uv::new_checker(1.0, 1.0, *c, *c)) as Arc<dyn UvPattern>).collect();
+    let m = uv::new_cube_map(faces[0].clone(), faces[1].clone(), faces[2].clone(),
+        faces[3].clone(), faces[4].clone(), faces[5].clone());
+
+    assert_eq!(m.color_at(primatives::point(-1.0, 0.0, 0.0)), colors[0]);
+    assert_eq!(m.color_at(primatives::point(1.0, 0.0, 0.0)), colors[1]);
+    assert_eq!(m.color_at(primatives::point(0.0, 0.0, 1.0)), colors[2]);
+    assert_eq!(m.color_at(primatives::point(0.0, 0.0, -1.0)), colors[3]);
+    assert_eq!(m.color_at(primatives::point(0.0, 1.0, 0.0)), colors[4]);
+    assert_eq!(m.color_at(primatives::point(0.0, -1.0, 0.0)), colors[5]);
+}
+
+#[test]
+fn texture_map_pattern() {
+    let checkers = Arc::new(uv::new_checker(16.0, 8.0, color::BLACK, white()));
+    let p = uv::new(checkers, Mapping::Spherical);
+
+    let cases = [
+        (primatives::point(0.4315, 0.4670, 0.7719), white()),
+        (primatives::point(-0.9654, 0.2552, -0.0534), color::BLACK),
+        (primatives::point(0.1039, 0.7090, 0.6975), white()),
+        (primatives::point(-0.4986, -0.7856, -0.3663), color::BLACK),
+        (primatives::point(-0.0317, -0.9395, 0.3411), color::BLACK),
+        (primatives::point(0.4809, -0.7721, 0.4154), color::BLACK),
+        (primatives::point(0.0285, -0.9612, -0.2745), color::BLACK),
+        (primatives::point(-0.5734, -0.2162, -0.7903), white()),
+        (primatives::point(0.7688, -0.1470, 0.6223), color::BLACK),
+        (primatives::point(-0.7652, 0.2175, 0.6060), color::BLACK),
+    ];
+
+    for (point, expected) in cases {
+        assert_eq!(p.color_at(point), expected);
+    }
+}
+
+#[test]
+fn image_texture() {
+    //A 10x10 image where each pixel is (x/10, y/10, 0)
+    let mut c = canvas::new(10, 10);
+    for y in 0..10 {
+        for x in 0..10 {
+            c.contents[y * 10 + x] = color::new(x as f64 / 10.0, y as f64 / 10.0, 0.0);
+        }
+    }
+
+    let texture = image::new(c).unwrap();
+    let cases = [
+        (0.0, 0.0, color::new(0.0, 0.9, 0.0)),
+        (0.3, 0.0, color::new(0.3, 0.9, 0.0)),
+        (0.6, 0.3, color::new(0.5, 0.6, 0.0)),
+        (1.0, 1.0, color::new(0.9, 0.0, 0.0)),
+    ];
+
+    for (u, v, expected) in cases {
+        assert_eq!(texture.uv_color_at(u, v), expected);
+    }
+}
+
+#[test]
+fn empty_image_texture() {
+    let e = image::new(canvas::new(0, 4)).unwrap_err();
+    assert_eq!(e, image::ImageError::Empty { width: 0, height: 4 });
+    assert_eq!(e.to_string(), "Image texture is empty (0 x 4)");
+}
+
+#[test]
+fn earth_on_a_sphere() {
+    //Two pixels, the first half of the image is blue and the second half is green
+    let filename = std::env::temp_dir().join("frog_texture_tests_earth.ppm");
+    let filename = filename.to_str().unwrap();
+    std::fs::write(filename, "P3\n2 1\n255\n0 0 255  0 255 0\n").unwrap();
+
+    let earth = image::load(filename).unwrap();
+    assert_eq!(earth.canvas.width, 2);
+    assert_eq!(earth.canvas.height, 1);
+
+    let mut s = transformations::new_translation_matrix(0.0, 0.0, 5.0) * sphere::new(1.0, primatives::point(0.0, 0.0, 0.0));
+    s.material.pattern = Some(Arc::new(uv::new(Arc::new(earth), Mapping::Spherical)));
+    s.material.ambient = 1.0;
+    s.material.diffuse = 0.0;
+    s.material.specular = 0.0;
+
+    let l = frog::light::new(white(), primatives::point(0.0, 0.0, -10.0));
+    let looking_at = |x: f64| {
+        let r = ray::new(primatives::point(x, 0.0, 0.0), primatives::vec3(0.0, 0.0, 1.0));
+        let i = intersection::hit(&s.intersect(r)).unwrap();
+        return frog::shading::shade_intersection(i, l, false);
+    };
+
+    //The image wraps all the way around, starting and ending on the side facing -z
+    assert_eq!(looking_at(0.5), color::new(0.0, 0.0, 1.0));
+    assert_eq!(looking_at(-0.5), color::new(0.0, 1.0, 0.0));
+
+    std::fs::remove_file(filename).unwrap();
+
+    //Files that can not be read come back as errors
+    match image::load(filename) {
+        Err(image::ImageError::Ppm(ppm::PpmError::Io(_))) => (),
+        other => panic!("Expected an IO error, got {:?}", other)
+    }
+}
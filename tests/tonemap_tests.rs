@@ -0,0 +1,126 @@
+#[cfg(test)]
+
+use frog::color;
+use frog::canvas;
+use frog::tonemap;
+use frog::tonemap::{Operator};
+
+#[test]
+fn linear_tone_map() {
+    let tm = tonemap::new_linear();
+    assert_eq!(tm.apply(color::new(0.5, 1.5, -0.5)), color::new(0.5, 1.0, 0.0));
+
+    //Matches the old clamp and round down
+    for v in [0.0, 0.1, 0.5, 0.999, 1.0, 3.0] {
+        let c = color::new(v, v, v);
+        assert_eq!(tm.to_rgb8(c, 0, 0)[0] as f64, c.scaled_from_1_to_255().red);
+    }
+}
+
+#[test]
+fn exposure() {
+    let mut tm = tonemap::new_linear();
+    tm.exposure = 1.0;
+    assert_eq!(tm.apply(color::new(0.25, 0.5, 1.0)), color::new(0.5, 1.0, 1.0));
+
+    tm.exposure = -2.0;
+    assert_eq!(tm.apply(color::new(0.25, 2.0, 1.0)), color::new(0.0625, 0.5, 0.25));
+}
+
+#[test]
+fn operators() {
+    let at = |op: Operator, v: f64| -> f64 {
+        let mut tm = tonemap::new(0.0, op);
+        tm.srgb = false;
+        return tm.apply(color::new(v, v, v)).red;
+    };
+
+    assert_eq!(at(Operator::Reinhard, 1.0), 0.5);
+    assert_eq!(at(Operator::Reinhard, 3.0), 0.75);
+    assert_eq!(at(Operator::Aces, 0.0), 0.0);
+    assert!((at(Operator::Aces, 1.0) - 0.80380).abs() < 0.0001);
+    assert_eq!(at(Operator::Filmic, 0.0), 0.0);
+    assert!((at(Operator::Filmic, 5.6) - 1.0).abs() < 0.0001);
+
+    //Every curve rises steadily, and never goes past white
+    for op in [Operator::Clamp, Operator::Reinhard, Operator::Aces, Operator::Filmic] {
+        let mut last = 0.0;
+        for step in 1..200 {
+            let v = at(op, step as f64 * 0.1);
+            assert!(v >= last);
+            assert!(v <= 1.0);
+            last = v;
+        }
+    }
+
+    //Only clamping loses the difference between bright values
+    assert_eq!(at(Operator::Clamp, 2.0), at(Operator::Clamp, 4.0));
+    assert!(at(Operator::Reinhard, 2.0) < at(Operator::Reinhard, 4.0));
+    assert!(at(Operator::Filmic, 2.0) < at(Operator::Filmic, 4.0));
+}
+
+#[test]
+fn srgb_gamma() {
+    assert_eq!(tonemap::srgb_encode(0.0), 0.0);
+    assert!((tonemap::srgb_encode(1.0) - 1.0).abs() < 0.0001);
+    assert!((tonemap::srgb_encode(0.002) - 0.02584).abs() < 0.0001);
+    assert!((tonemap::srgb_encode(0.5) - 0.73536).abs() < 0.0001);
+    assert!((tonemap::srgb_encode(0.18) - 0.46135).abs() < 0.0001);
+
+    //Brightens midtones
+    let tm = tonemap::new(0.0, Operator::Clamp);
+    assert_eq!(tm.to_rgb8(color::new(0.18, 0.5, 1.0), 0, 0), [117, 187, 255]);
+}
+
+#[test]
+fn dithering() {
+    let mut tm = tonemap::new_linear();
+    tm.dither = true;
+
+    //A value between two steps lands on either, about in proportion
+    let c = color::new(100.25 / 255.0, 0.0, 1.0);
+    let mut high = 0;
+    for y in 0..100 {
+        for x in 0..100 {
+            let bytes = tm.to_rgb8(c, x, y);
+            assert!(bytes[0] == 100 || bytes[0] == 101);
+            assert_eq!(bytes[1], 0);
+            assert_eq!(bytes[2], 255);
+
+            if bytes[0] == 101 {
+                high += 1;
+            }
+        }
+    }
+    assert!(high > 2000 && high < 3000);
+
+    //Repeatable
+    assert_eq!(tm.to_rgb8(c, 3, 4), tm.to_rgb8(c, 3, 4));
+}
+
+#[test]
+fn canvas_exports_through_tone_map() {
+    let mut c = canvas::new(2, 1);
+    c.contents[0] = color::new(0.5, 1.0, 3.0);
+    c.contents[1] = color::new(0.18, 0.0, 0.0);
+
+    assert_eq!(c.to_rgb8(), vec![127, 255, 255, 45, 0, 0]);
+
+    c.tone_map = tonemap::new(0.0, Operator::Reinhard);
+    let expected: Vec<u8> = c.contents.iter().flat_map(|p| c.tone_map.to_rgb8(*p, 0, 0)).collect();
+    assert_eq!(c.to_rgb8(), expected);
+
+    let mut p6 = vec![];
+    c.write_to(&mut p6, canvas::Format::P6).unwrap();
+    assert_eq!(p6[(p6.len() - 6)..], expected[..]);
+
+    let mut p3 = vec![];
+    c.write_to(&mut p3, canvas::Format::P3).unwrap();
+    let text = String::from_utf8(p3).unwrap();
+    assert_eq!(text.lines().nth(3).unwrap(), format!("{} {} {}", expected[0], expected[1], expected[2]));
+
+    //Float formats keep the raw light
+    let mut pfm = vec![];
+    c.write_to(&mut pfm, canvas::Format::Pfm).unwrap();
+    assert_eq!(pfm[(pfm.len() - 16)..(pfm.len() - 12)], 3.0_f32.to_le_bytes());
+}
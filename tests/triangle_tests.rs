@@ -0,0 +1,130 @@
+#[cfg(test)]
+
+use frog::primatives;
+use frog::shapes::triangle;
+use frog::shapes::{Shape, Surface};
+use frog::shapes::sphere;
+use frog::ray;
+use frog::matrix;
+use frog::matrix::transformations;
+use frog::material;
+
+fn test_triangle() -> triangle::Triangle {
+    return triangle::new(primatives::point(0.0, 1.0, 0.0), primatives::point(-1.0, 0.0, 0.0), primatives::point(1.0, 0.0, 0.0));
+}
+
+fn test_smooth_triangle() -> triangle::SmoothTriangle {
+    return triangle::new_smooth(
+        primatives::point(0.0, 1.0, 0.0), primatives::point(-1.0, 0.0, 0.0), primatives::point(1.0, 0.0, 0.0),
+        primatives::vec3(0.0, 1.0, 0.0), primatives::vec3(-1.0, 0.0, 0.0), primatives::vec3(1.0, 0.0, 0.0));
+}
+
+#[test]
+fn triangle_creation() {
+    let t = test_triangle();
+    assert_eq!(t.e1, primatives::vec3(-1.0, -1.0, 0.0));
+    assert_eq!(t.e2, primatives::vec3(1.0, -1.0, 0.0));
+    assert_eq!(t.normal, primatives::vec3(0.0, 0.0, -1.0));
+    assert_eq!(t.transformation, matrix::IDENTITY_MATRIX_4X4);
+    assert_eq!(t.material, material::new_default());
+
+    let b = t.bounds();
+    assert_eq!(b.min, primatives::point(-1.0, 0.0, 0.0));
+    assert_eq!(b.max, primatives::point(1.0, 1.0, 0.0));
+
+    let t = transformations::new_translation_matrix(0.0, 0.0, 2.0) * t;
+    assert_eq!(t.bounds().min, primatives::point(-1.0, 0.0, 2.0));
+}
+
+#[test]
+#[should_panic]
+fn triangle_with_vector_corner() {
+    triangle::new(primatives::point(0.0, 1.0, 0.0), primatives::vec3(-1.0, 0.0, 0.0), primatives::point(1.0, 0.0, 0.0));
+}
+
+#[test]
+fn triangle_normal() {
+    let t = test_triangle();
+
+    //The normal is the same all over a flat triangle
+    for p in [primatives::point(0.0, 0.5, 0.0), primatives::point(-0.5, 0.75, 0.0), primatives::point(0.5, 0.25, 0.0)] {
+        let n = t.normal_at(p);
+        n.check_type(primatives::TYPE_VEC);
+        assert_eq!(n, t.normal);
+    }
+
+    let t = transformations::new_rotation_y_matrix(transformations::PI / 2.0) * test_triangle();
+    assert_eq!(t.normal_at(primatives::point(0.0, 0.5, 0.0)), primatives::vec3(-1.0, 0.0, 0.0));
+}
+
+#[test]
+fn ray_triangle_intersection() {
+    let t = test_triangle();
+
+    let misses = [
+        //Parallel to the triangle
+        (primatives::point(0.0, -1.0, -2.0), primatives::vec3(0.0, 1.0, 0.0)),
+        //Past each of the three edges
+        (primatives::point(1.0, 1.0, -2.0), primatives::vec3(0.0, 0.0, 1.0)),
+        (primatives::point(-1.0, 1.0, -2.0), primatives::vec3(0.0, 0.0, 1.0)),
+        (primatives::point(0.0, -1.0, -2.0), primatives::vec3(0.0, 0.0, 1.0)),
+    ];
+    for (origin, direction) in misses {
+        assert_eq!(t.intersect(ray::new(origin, direction)).len(), 0);
+    }
+
+    let r = ray::new(primatives::point(0.0, 0.5, -2.0), primatives::vec3(0.0, 0.0, 1.0));
+    let intersections = t.intersect(r);
+    assert_eq!(intersections.len(), 1);
+    assert_eq!(intersections[0].time, 2.0);
+    assert_eq!(intersections[0].location, primatives::point(0.0, 0.5, 0.0));
+
+    //Moved away from the ray
+    let t = transformations::new_translation_matrix(5.0, 0.0, 0.0) * test_triangle();
+    assert_eq!(t.intersect(r).len(), 0);
+}
+
+#[test]
+fn intersection_uv() {
+    let t = test_smooth_triangle();
+    let r = ray::new(primatives::point(-0.2, 0.3, -5.0), primatives::vec3(0.0, 0.0, 1.0));
+    let intersections = t.intersect(r);
+    assert_eq!(intersections.len(), 1);
+    assert!((intersections[0].u - 0.45).abs() < 0.0001);
+    assert!((intersections[0].v - 0.25).abs() < 0.0001);
+
+    //Flat triangles keep u and v too, but other shapes leave them at zero
+    let flat = test_triangle();
+    let intersections = flat.intersect(r);
+    assert!((intersections[0].u - 0.45).abs() < 0.0001);
+    assert!((intersections[0].v - 0.25).abs() < 0.0001);
+
+    let s = sphere::new(1.0, primatives::point(0.0, 0.0, 0.0));
+    let intersections = s.intersect(r);
+    assert_eq!(intersections[0].u, 0.0);
+    assert_eq!(intersections[0].v, 0.0);
+}
+
+#[test]
+fn smooth_triangle_normal() {
+    let t = test_smooth_triangle();
+
+    //Blends the normals at each corner
+    let n = t.normal_at_uv(primatives::point(0.0, 0.0, 0.0), 0.45, 0.25);
+    n.check_type(primatives::TYPE_VEC);
+    assert_eq!(n, primatives::vec3(-0.5547, 0.83205, 0.0));
+
+    //The same normal is worked out from the point alone
+    let p = primatives::point(-0.2, 0.3, 0.0);
+    assert_eq!(t.normal_at(p), primatives::vec3(-0.5547, 0.83205, 0.0));
+
+    //Intersections use the interpolated normal, flipped towards the eye
+    let r = ray::new(primatives::point(-0.2, 0.3, -5.0), primatives::vec3(0.0, 0.0, 1.0));
+    let intersections = t.intersect(r);
+    assert_eq!(intersections[0].normalv, primatives::vec3(-0.5547, 0.83205, 0.0));
+
+    //At the corners, the normal is exactly the corner's normal
+    assert_eq!(t.normal_at(t.p1), t.n1);
+    assert_eq!(t.normal_at(t.p2), t.n2);
+    assert_eq!(t.normal_at(t.p3), t.n3);
+}
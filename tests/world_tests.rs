@@ -2,7 +2,9 @@
 
 use frog::world;
 use frog::shapes::sphere;
+use frog::shapes::plane;
 use frog::shapes::{Shape};
+use frog::shapes::intersection;
 use frog::primatives;
 use frog::color;
 use frog::matrix::transformations;
@@ -70,14 +72,14 @@ fn shade_intersection() {
 
     let intersects = s1.intersect(r);
 
-    let c = shading::shade_intersection(intersects[1], w.light);
+    let c = shading::shade_intersection(intersects[1], w.lights[0], false);
     assert_eq!(c, color::new(0.38066, 0.47583, 0.2855));
 
-    w.light = light::new(color::new(1.0, 1.0, 1.0), primatives::point(0.0, 0.25, 0.0));
+    w.lights = vec![light::new(color::new(1.0, 1.0, 1.0), primatives::point(0.0, 0.25, 0.0))];
     let r = ray::new(primatives::point(0.0, 0.0, 0.0), primatives::vec3(0.0, 0.0, 1.0));
 
     let intersects = s2.intersect(r);
-    let c = shading::shade_intersection(intersects[0], w.light);
+    let c = shading::shade_intersection(intersects[0], w.lights[0], false);
 
     assert_eq!(c, color::new(0.90498, 0.90498, 0.90498));
 
@@ -87,6 +89,371 @@ fn shade_intersection() {
     assert_eq!(c, color::new(0.0, 0.0, 0.0));
 }
 
+#[test]
+fn shadows() {
+    let c = camera::new(0, 0, 0.0);
+    let mut w = world::new(c);
+
+    let mut s1 = sphere::new(1.0, primatives::point(0.0, 0.0, 0.0));
+    s1.material.color = color::new(0.8, 1.0, 0.6);
+    s1.material.diffuse = 0.7;
+    s1.material.specular = 0.2;
+    w.objects.push(&s1);
+
+    let s2 = transformations::new_scaling_matrix(0.5, 0.5, 0.5) * sphere::new(1.0, primatives::point(0.0, 0.0, 0.0));
+    w.objects.push(&s2);
+
+    //Nothing is colinear with the point and the light
+    assert_eq!(w.is_shadowed(primatives::point(0.0, 10.0, 0.0), w.lights[0]), false);
+
+    //The spheres are between the point and the light
+    assert_eq!(w.is_shadowed(primatives::point(10.0, -10.0, 10.0), w.lights[0]), true);
+
+    //The light is between the point and the spheres
+    assert_eq!(w.is_shadowed(primatives::point(-20.0, 20.0, -20.0), w.lights[0]), false);
+
+    //The point is between the light and the spheres
+    assert_eq!(w.is_shadowed(primatives::point(-2.0, 2.0, -2.0), w.lights[0]), false);
+
+    //A sphere sitting in the shadow of another only gets ambient light
+    let c = camera::new(0, 0, 0.0);
+    let mut w = world::new(c);
+    w.lights = vec![light::new(color::new(1.0, 1.0, 1.0), primatives::point(0.0, 0.0, -10.0))];
+
+    let s3 = sphere::new(1.0, primatives::point(0.0, 0.0, 0.0));
+    let s4 = transformations::new_translation_matrix(0.0, 0.0, 10.0) * sphere::new(1.0, primatives::point(0.0, 0.0, 0.0));
+    w.objects.push(&s3);
+    w.objects.push(&s4);
+
+    let r = ray::new(primatives::point(0.0, 0.0, 5.0), primatives::vec3(0.0, 0.0, 1.0));
+    assert_eq!(w.color_at_ray(r), color::new(0.1, 0.1, 0.1));
+
+    //The hit point is pushed off of the surface, so it does not shadow itself
+    let r = ray::new(primatives::point(0.0, 0.0, -5.0), primatives::vec3(0.0, 0.0, 1.0));
+    let s5 = transformations::new_translation_matrix(0.0, 0.0, 1.0) * sphere::new(1.0, primatives::point(0.0, 0.0, 0.0));
+    let hit = intersection::hit(&s5.intersect(r)).unwrap();
+
+    assert_eq!(hit.time, 5.0);
+    assert!(hit.over_point.z < -intersection::OVER_POINT_EPSILON / 2.0);
+    assert!(hit.location.z > hit.over_point.z);
+}
+
+#[test]
+fn reflections() {
+    let c = camera::new(0, 0, 0.0);
+    let mut w = world::new(c);
+
+    let mut s1 = sphere::new(1.0, primatives::point(0.0, 0.0, 0.0));
+    s1.material.color = color::new(0.8, 1.0, 0.6);
+    s1.material.diffuse = 0.7;
+    s1.material.specular = 0.2;
+    w.objects.push(&s1);
+
+    let mut s2 = transformations::new_scaling_matrix(0.5, 0.5, 0.5) * sphere::new(1.0, primatives::point(0.0, 0.0, 0.0));
+    s2.material.ambient = 1.0;
+    w.objects.push(&s2);
+
+    //The reflected vector bounces off at the same angle it came in
+    let half_sqrt_2 = 2.0_f64.sqrt() / 2.0;
+    let p = plane::new();
+    let r = ray::new(primatives::point(0.0, 1.0, -1.0), primatives::vec3(0.0, -half_sqrt_2, half_sqrt_2));
+    let i = p.intersect(r)[0];
+    assert_eq!(i.reflectv, primatives::vec3(0.0, half_sqrt_2, half_sqrt_2));
+
+    //A non-reflective surface reflects nothing
+    let r = ray::new(primatives::point(0.0, 0.0, 0.0), primatives::vec3(0.0, 0.0, 1.0));
+    let i = s2.intersect(r)[0];
+    assert_eq!(w.reflected_color(i, world::DEFAULT_MAX_RECURSION_DEPTH), color::BLACK);
+
+    //A reflective floor picks up the color of the spheres
+    let mut floor = transformations::new_translation_matrix(0.0, -1.0, 0.0) * plane::new();
+    floor.material.reflective = 0.5;
+    w.objects.push(&floor);
+
+    let r = ray::new(primatives::point(0.0, 0.0, -3.0), primatives::vec3(0.0, -half_sqrt_2, half_sqrt_2));
+    let i = floor.intersect(r)[0];
+    assert_eq!(w.reflected_color(i, world::DEFAULT_MAX_RECURSION_DEPTH), color::new(0.19032, 0.2379, 0.14274));
+    assert_eq!(w.color_at_ray(r), color::new(0.87677, 0.92436, 0.82918));
+
+    //Once the recursion limit is reached, nothing more is reflected
+    assert_eq!(w.reflected_color(i, 0), color::BLACK);
+}
+
+#[test]
+fn facing_mirrors() {
+    let c = camera::new(0, 0, 0.0);
+    let mut w = world::new(c);
+    w.lights = vec![light::new(color::new(1.0, 1.0, 1.0), primatives::point(0.0, 0.0, 0.0))];
+
+    let mut lower = transformations::new_translation_matrix(0.0, -1.0, 0.0) * plane::new();
+    lower.material.reflective = 1.0;
+
+    let mut upper = transformations::new_translation_matrix(0.0, 1.0, 0.0) * plane::new();
+    upper.material.reflective = 1.0;
+
+    w.objects.push(&lower);
+    w.objects.push(&upper);
+
+    //Should bounce until the depth limit, rather than recursing forever
+    let r = ray::new(primatives::point(0.0, 0.0, 0.0), primatives::vec3(0.0, 1.0, 0.0));
+    let c = w.color_at_ray(r);
+    assert!(c.red > 0.0);
+}
+
+#[test]
+fn multiple_lights() {
+    let c = camera::new(0, 0, 0.0);
+    let mut w = world::new(c);
+    assert_eq!(w.lights.len(), 1);
+
+    let s = sphere::new(1.0, primatives::point(0.0, 0.0, 0.0));
+    let blocker = sphere::new(1.0, primatives::point(0.0, 0.0, -5.0));
+    w.objects.push(&s);
+    w.objects.push(&blocker);
+    w.lights = vec![light::new(color::new(0.5, 0.5, 0.5), primatives::point(0.0, 10.0, -3.0))];
+
+    let r = ray::new(primatives::point(0.0, 0.0, -2.0), primatives::vec3(0.0, 0.0, 1.0));
+    let key_only = w.color_at_ray(r);
+
+    //A second, identical light doubles the light reaching the point
+    w.add_light(light::new(color::new(0.5, 0.5, 0.5), primatives::point(0.0, 10.0, -3.0)));
+    assert_eq!(w.lights.len(), 2);
+    assert_eq!(w.color_at_ray(r), key_only * 2.0);
+
+    //A light hidden behind the blocker only adds its ambient term
+    w.add_light(light::new(color::new(1.0, 1.0, 1.0), primatives::point(0.0, 0.0, -10.0)));
+    assert_eq!(w.color_at_ray(r), key_only * 2.0 + color::new(0.1, 0.1, 0.1));
+
+    let removed = w.remove_light(1);
+    assert_eq!(removed, light::new(color::new(0.5, 0.5, 0.5), primatives::point(0.0, 10.0, -3.0)));
+    assert_eq!(w.lights.len(), 2);
+    assert_eq!(w.color_at_ray(r), key_only + color::new(0.1, 0.1, 0.1));
+
+    //With no lights at all, nothing can be seen
+    w.remove_light(1);
+    w.remove_light(0);
+    assert_eq!(w.color_at_ray(r), color::BLACK);
+}
+
+#[test]
+fn soft_shadows() {
+    let c = camera::new(0, 0, 0.0);
+    let mut w = world::new(c);
+
+    //A 2x2 light overhead, and a small ball casting a shadow onto the floor
+    w.lights = vec![light::new_area(color::new(1.0, 1.0, 1.0), primatives::point(-1.0, 10.0, -1.0),
+        primatives::vec3(2.0, 0.0, 0.0), primatives::vec3(0.0, 0.0, 2.0), 8)];
+
+    let floor = plane::new();
+    let ball = sphere::new(1.0, primatives::point(0.0, 5.0, 0.0));
+    w.objects.push(&floor);
+    w.objects.push(&ball);
+
+    let down = primatives::vec3(0.0, -1.0, 0.0);
+    let lit = w.color_at_ray(ray::new(primatives::point(5.0, 1.0, 0.0), down));
+    let umbra = w.color_at_ray(ray::new(primatives::point(0.0, 1.0, 0.0), down));
+    let penumbra = w.color_at_ray(ray::new(primatives::point(1.2, 1.0, 0.0), down));
+
+    assert_eq!(umbra, color::new(0.1, 0.1, 0.1));
+    assert!(penumbra.red > umbra.red);
+    assert!(penumbra.red < lit.red);
+}
+
+#[test]
+fn directional_and_spot_shadows() {
+    let c = camera::new(0, 0, 0.0);
+    let mut w = world::new(c);
+
+    let floor = plane::new();
+    let ball = sphere::new(1.0, primatives::point(0.0, 500.0, 0.0));
+    w.objects.push(&floor);
+    w.objects.push(&ball);
+
+    //However far away the blocker is, it still shades a directional light
+    w.lights = vec![light::new_directional(color::new(1.0, 1.0, 1.0), primatives::vec3(0.0, -1.0, 0.0))];
+    assert_eq!(w.is_shadowed(primatives::point(0.0, 0.0001, 0.0), w.lights[0]), true);
+    assert_eq!(w.is_shadowed(primatives::point(2.0, 0.0001, 0.0), w.lights[0]), false);
+
+    //A spot light under the blocker is not shaded by it
+    w.lights = vec![light::new_spot(color::new(1.0, 1.0, 1.0), primatives::point(0.0, 10.0, 0.0),
+        primatives::vec3(0.0, -1.0, 0.0), transformations::PI / 8.0, transformations::PI / 4.0)];
+    assert_eq!(w.is_shadowed(primatives::point(0.0, 0.0001, 0.0), w.lights[0]), false);
+
+    let down = primatives::vec3(0.0, -1.0, 0.0);
+    let centre = w.color_at_ray(ray::new(primatives::point(0.0, 1.0, 0.0), down));
+    let outside = w.color_at_ray(ray::new(primatives::point(50.0, 1.0, 0.0), down));
+    assert!(centre.red > 0.9);
+    assert_eq!(outside, color::new(0.0, 0.0, 0.0));
+}
+
+fn assert_sync<T: Sync>() {}
+
+#[test]
+fn parallel_render() {
+    //These are shared between the render threads
+    assert_sync::<world::World>();
+    assert_sync::<camera::Camera>();
+    assert_sync::<&dyn Shape>();
+
+    let c = camera::new(30, 40, transformations::PI / 3.0);
+    let mut w = world::new(c);
+    w.camera.transformation = transformations::new_view_transformation_matrix(
+        primatives::point(0.0, 1.5, -5.0), primatives::point(0.0, 1.0, 0.0), primatives::vec3(0.0, 1.0, 0.0));
+
+    let mut floor = plane::new();
+    floor.material.reflective = 0.3;
+    let mut s1 = sphere::new(1.0, primatives::point(-0.5, 1.0, 0.5));
+    s1.material.color = color::new(0.1, 1.0, 0.5);
+    let mut s2 = glass_sphere();
+    s2.origin = primatives::point(1.5, 0.5, -0.5);
+    s2.radius = 0.5;
+
+    w.objects.push(&floor);
+    w.objects.push(&s1);
+    w.objects.push(&s2);
+    w.add_light(light::new_area(color::new(0.5, 0.5, 0.5), primatives::point(5.0, 10.0, -10.0),
+        primatives::vec3(2.0, 0.0, 0.0), primatives::vec3(0.0, 2.0, 0.0), 2));
+
+    let serial = w.render_to_canvas_serial();
+    assert_eq!(w.render_to_canvas_with_threads(1).contents, serial.contents);
+    assert_eq!(w.render_to_canvas_with_threads(4).contents, serial.contents);
+    assert_eq!(w.render_to_canvas_with_threads(100).contents, serial.contents);
+    assert_eq!(w.render_to_canvas().contents, serial.contents);
+
+    //Make sure the scene was actually in view
+    assert!(serial.contents.iter().any(|c| c.red > 0.0 || c.green > 0.0 || c.blue > 0.0));
+
+    //A canvas with no pixels does not need any threads
+    let w = world::new(camera::new(0, 0, 0.0));
+    assert_eq!(w.render_to_canvas_with_threads(4).contents.len(), 0);
+}
+
+fn glass_sphere() -> sphere::Sphere {
+    let mut s = sphere::new(1.0, primatives::point(0.0, 0.0, 0.0));
+    s.material.transparency = 1.0;
+    s.material.refractive_index = 1.5;
+    return s;
+}
+
+#[test]
+fn refractive_indices() {
+    let c = camera::new(0, 0, 0.0);
+    let mut w = world::new(c);
+
+    let mut a = transformations::new_scaling_matrix(2.0, 2.0, 2.0) * glass_sphere();
+    a.material.refractive_index = 1.5;
+    let mut b = transformations::new_translation_matrix(0.0, 0.0, -0.25) * glass_sphere();
+    b.material.refractive_index = 2.0;
+    let mut c = transformations::new_translation_matrix(0.0, 0.0, 0.25) * glass_sphere();
+    c.material.refractive_index = 2.5;
+
+    w.objects.push(&a);
+    w.objects.push(&b);
+    w.objects.push(&c);
+
+    let r = ray::new(primatives::point(0.0, 0.0, -4.0), primatives::vec3(0.0, 0.0, 1.0));
+    let intersections = w.intersect(r);
+    assert_eq!(intersections.len(), 6);
+
+    let expected = [(1.0, 1.5), (1.5, 2.0), (2.0, 2.5), (2.5, 2.5), (2.5, 1.5), (1.5, 1.0)];
+    for (i, (n1, n2)) in expected.iter().enumerate() {
+        let prepared = intersection::refractive_indices(intersections[i], &intersections);
+        assert_eq!(prepared.n1, *n1);
+        assert_eq!(prepared.n2, *n2);
+    }
+
+    //The under point sits just below the surface
+    let s = transformations::new_translation_matrix(0.0, 0.0, 1.0) * glass_sphere();
+    let r = ray::new(primatives::point(0.0, 0.0, -5.0), primatives::vec3(0.0, 0.0, 1.0));
+    let hit = intersection::hit(&s.intersect(r)).unwrap();
+    assert!(hit.under_point.z > intersection::OVER_POINT_EPSILON / 2.0);
+    assert!(hit.location.z < hit.under_point.z);
+}
+
+#[test]
+fn refractions() {
+    let c = camera::new(0, 0, 0.0);
+    let mut w = world::new(c);
+
+    let mut s1 = sphere::new(1.0, primatives::point(0.0, 0.0, 0.0));
+    s1.material.color = color::new(0.8, 1.0, 0.6);
+    s1.material.diffuse = 0.7;
+    s1.material.specular = 0.2;
+    w.objects.push(&s1);
+
+    let s2 = transformations::new_scaling_matrix(0.5, 0.5, 0.5) * sphere::new(1.0, primatives::point(0.0, 0.0, 0.0));
+    w.objects.push(&s2);
+
+    //Opaque surfaces let no light through
+    let r = ray::new(primatives::point(0.0, 0.0, -5.0), primatives::vec3(0.0, 0.0, 1.0));
+    let xs = w.intersect(r);
+    let i = intersection::refractive_indices(xs[0], &xs);
+    assert_eq!(w.refracted_color(i, world::DEFAULT_MAX_RECURSION_DEPTH), color::BLACK);
+
+    //Past the critical angle, there is total internal reflection
+    let half_sqrt_2 = 2.0_f64.sqrt() / 2.0;
+    let glass = glass_sphere();
+    let mut glass_world = world::new(camera::new(0, 0, 0.0));
+    glass_world.objects.push(&glass);
+
+    let r = ray::new(primatives::point(0.0, 0.0, half_sqrt_2), primatives::vec3(0.0, 1.0, 0.0));
+    let xs = glass_world.intersect(r);
+    let i = intersection::refractive_indices(xs[1], &xs);
+    assert_eq!(glass_world.refracted_color(i, world::DEFAULT_MAX_RECURSION_DEPTH), color::BLACK);
+
+    //Once the recursion limit is reached, nothing more is refracted
+    let r = ray::new(primatives::point(0.0, 0.0, -5.0), primatives::vec3(0.0, 0.0, 1.0));
+    let xs = glass_world.intersect(r);
+    let i = intersection::refractive_indices(xs[0], &xs);
+    assert_eq!(glass_world.refracted_color(i, 0), color::BLACK);
+
+    //Looking through a glass floor at a ball beneath it
+    let mut floor = transformations::new_translation_matrix(0.0, -1.0, 0.0) * plane::new();
+    floor.material.transparency = 0.5;
+    floor.material.refractive_index = 1.5;
+    w.objects.push(&floor);
+
+    let mut ball = sphere::new(1.0, primatives::point(0.0, -3.5, -0.5));
+    ball.material.color = color::new(1.0, 0.0, 0.0);
+    ball.material.ambient = 0.5;
+    w.objects.push(&ball);
+
+    let r = ray::new(primatives::point(0.0, 0.0, -3.0), primatives::vec3(0.0, -half_sqrt_2, half_sqrt_2));
+    assert_eq!(w.color_at_ray(r), color::new(0.93642, 0.68642, 0.68642));
+
+    //A reflective, transparent floor blends the two using Schlick's approximation
+    let mut shiny_floor = floor.clone();
+    shiny_floor.material.reflective = 0.5;
+    w.objects[2] = &shiny_floor;
+    assert_eq!(w.color_at_ray(r), color::new(0.93391, 0.69643, 0.69243));
+}
+
+#[test]
+fn schlick_approximation() {
+    let glass = glass_sphere();
+    let mut w = world::new(camera::new(0, 0, 0.0));
+    w.objects.push(&glass);
+
+    //Under total internal reflection, everything is reflected
+    let half_sqrt_2 = 2.0_f64.sqrt() / 2.0;
+    let r = ray::new(primatives::point(0.0, 0.0, half_sqrt_2), primatives::vec3(0.0, 1.0, 0.0));
+    let xs = w.intersect(r);
+    let i = intersection::refractive_indices(xs[1], &xs);
+    assert_eq!(shading::schlick(i), 1.0);
+
+    //Looking straight on, very little is reflected
+    let r = ray::new(primatives::point(0.0, 0.0, 0.0), primatives::vec3(0.0, 1.0, 0.0));
+    let xs = w.intersect(r);
+    let i = intersection::refractive_indices(xs[1], &xs);
+    assert!((shading::schlick(i) - 0.04).abs() < 0.0001);
+
+    //At a shallow angle, much more is reflected
+    let r = ray::new(primatives::point(0.0, 0.99, -2.0), primatives::vec3(0.0, 0.0, 1.0));
+    let xs = w.intersect(r);
+    let i = intersection::refractive_indices(xs[0], &xs);
+    assert!((shading::schlick(i) - 0.48873).abs() < 0.0001);
+}
+
 #[test]
 #[ignore]
 fn draw_world() {
@@ -107,7 +474,7 @@ fn draw_world() {
     w.objects.push(&s1);
     w.objects.push(&s2);
     w.objects.push(&s3);
-    w.light.location = primatives::point(-400.0, -400.0, 1000.0);
+    w.lights[0].location = primatives::point(-400.0, -400.0, 1000.0);
 
     let mut can = canvas::new(1000, 1000);
     